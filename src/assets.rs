@@ -0,0 +1,118 @@
+use std::fs;
+use std::borrow::Cow;
+use std::path::{ Path, PathBuf };
+
+static APP_DIRECTORY: &str = "wordsearch";
+
+//Built into the program so it works from any directory, files in the data directories replace them
+static ICON: &[u8] = include_bytes!("../data/icon.png");
+static DEFAULT_PREFERENCES: &str = include_str!("../data/defaults/preferences.json");
+static WORD_LISTS: [(&str, &str); 4] = [
+    ("Animals", include_str!("../data/word_lists/Animals.txt")),
+    ("Colors", include_str!("../data/word_lists/Colors.txt")),
+    ("Fruits", include_str!("../data/word_lists/Fruits.txt")),
+    ("Weather", include_str!("../data/word_lists/Weather.txt"))
+];
+
+//Searched in order: the user data directory (e.g. ~/.local/share/wordsearch on Linux),
+//the data folder next to the program and the data folder in the current directory
+pub fn get_directories(name: &str) -> Vec<PathBuf> {
+    let mut directories: Vec<PathBuf> = Vec::new();
+    if let Some(val) = dirs::data_dir() {
+        directories.push(val.join(APP_DIRECTORY));
+    }
+    if let Some(val) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(|parent| parent.join("data"))) {
+        directories.push(val);
+    }
+    if let Ok(val) = std::env::current_dir() {
+        directories.push(val.join("data"));
+    }
+
+    let mut found: Vec<PathBuf> = Vec::new();
+    for directory in directories {
+        let directory = directory.join(name);
+        if !found.contains(&directory) {
+            found.push(directory);
+        }
+    }
+
+    found
+}
+
+//The first file with the name in one of the data directories
+pub fn find_file(directory: &str, file_name: &str) -> Option<PathBuf> {
+    get_directories(directory).into_iter().map(|dir| dir.join(file_name)).find(|path| path.is_file())
+}
+
+//Files of every data directory with one of the extensions, a name that is found twice is only listed the first time
+pub fn list_files(directory: &str, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for dir in get_directories(directory) {
+        let entries = match fs::read_dir(&dir) {
+            Ok(val) => val,
+            Err(_) => continue
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let matches = match path.extension() {
+                Some(val) => extensions.iter().any(|extension| val.eq_ignore_ascii_case(extension)),
+                None => false
+            };
+            if matches && path.is_file() && !files.iter().any(|file| file.file_name() == path.file_name()) {
+                files.push(path);
+            }
+        }
+    }
+
+    files
+}
+
+pub fn get_icon() -> Cow<'static, [u8]> {
+    match find_file("", "icon.png").and_then(|path| fs::read(path).ok()) {
+        Some(val) => Cow::Owned(val),
+        None => Cow::Borrowed(ICON)
+    }
+}
+
+pub fn get_builtin_preferences() -> &'static str {
+    DEFAULT_PREFERENCES
+}
+
+pub fn get_default_preferences() -> Cow<'static, str> {
+    match find_file("defaults", "preferences.json").and_then(|path| fs::read_to_string(path).ok()) {
+        Some(val) => Cow::Owned(val),
+        None => Cow::Borrowed(DEFAULT_PREFERENCES)
+    }
+}
+
+//Built in lists followed by the .txt files in the word_lists data directories
+pub fn get_word_list_names() -> Vec<String> {
+    let mut names: Vec<String> = WORD_LISTS.iter().map(|(name, _)| name.to_string()).collect();
+    for path in list_files("word_lists", &["txt"]) {
+        if let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy().to_string()) {
+            if !names.contains(&stem) {
+                names.push(stem);
+            }
+        }
+    }
+
+    names
+}
+
+//Finds a word list by name, e.g. "Animals". Returns the text and the path of the file if it isn't built in
+pub fn load_word_list(name: &str) -> Option<(Cow<'static, str>, Option<PathBuf>)> {
+    if name.contains(['/', '\\']) || Path::new(name).extension().is_some() { //Paths are never names
+        return None;
+    }
+
+    if let Some(path) = find_file("word_lists", &format!("{}.txt", name)) {
+        if let Ok(text) = fs::read_to_string(&path) {
+            return Some((Cow::Owned(text), Some(path)));
+        }
+    }
+
+    WORD_LISTS.iter()
+        .find(|(list_name, _)| list_name.eq_ignore_ascii_case(name))
+        .map(|(_, text)| (Cow::Borrowed(*text), None))
+}
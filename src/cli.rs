@@ -0,0 +1,503 @@
+use std::fmt::{ self, Display, Formatter };
+use std::ops::RangeInclusive;
+use log::LevelFilter;
+use chrono::Local;
+
+use crate::word_list::{ self, SearchType, Language };
+use crate::request::{ self, WordSearchRequest };
+use crate::config::{ self, Preferences, WORD_COUNT_RANGE, LETTER_COUNT_RANGE };
+use crate::pdf::{ self, Layout, PdfOptions, PageSetup, Margins, WordListOptions, WordOrder };
+use crate::html;
+use crate::text::{ self, TextOptions };
+use crate::fonts;
+use crate::output::{ self, FileOptions };
+use crate::assets;
+use crate::logging;
+use crate::word_search::{ self, PuzzleKind, Directions };
+
+static USAGE: &str = "Usage: wordsearch [OPTIONS] <TYPE>:<WORD>...
+
+Creates one word search for every <TYPE>:<WORD> pair, e.g. \"rhymes:cat\".
+Without any arguments the graphical interface is started.
+
+Types:
+    related     Words related to <WORD>
+    rhymes      Words that rhyme with <WORD>
+    ends        Words ending with the letters <WORD>
+    sounds      Words that sound like <WORD>
+    before      Words that come before <WORD>
+    follows     Words that follow <WORD>
+    list        Words from the file <WORD>, one per line and optionally
+                followed by a picture: \"word, picture.png\". Instead of a file
+                a built in list can be used: {lists}
+
+Options:
+    -k, --kind <KIND>        Puzzle type: wordsearch, fillin or crisscross (default: wordsearch)
+    -w, --words <COUNT>      Maximum number of words in each list ({words})
+    -l, --letters <COUNT>    Number of letters per row ({letters})
+    -f, --format <FORMAT>    Output format: text, markdown, html or pdf (default: text)
+    -o, --output <DIR>       Write the output file to <DIR> instead of stdout, missing directories are created
+    -n, --name <TEMPLATE>    Output file name without extension, placeholders: {date}, {time}, {title}, {n} (default: wordsearch)
+        --split              Write one file per puzzle
+        --overwrite          Replace existing files instead of adding a number to the name
+    -p, --page <FORMAT>      Page format for pdf output: Letter, Half Letter, DINA4 or DINA5
+    -s, --size <SIZE>        Custom page size for pdf output, e.g. 210x297mm or 8.5x11in
+    -m, --margins <MM>       Page margins in mm, either one value or \"top,right,bottom,left\" (default: 15)
+        --landscape          Use landscape pages for pdf output
+        --header <TEMPLATE>  Page header for pdf output, placeholders: {title}, {class}, {date}, {page}, {pages}
+        --footer <TEMPLATE>  Page footer for pdf output, \"\" leaves it out
+        --class <NAME>       Class name used for the {class} placeholder
+    -L, --layout <LAYOUT>    Pages per sheet for pdf output: 1, 2, 4 or booklet (default: 1)
+        --sort <ORDER>       Order of the pdf word list: added, alphabetical or length (default: added)
+        --checkboxes         Print a checkbox next to every word of the pdf word list
+        --clues              Print definitions instead of the words in the pdf word list
+        --pictures           Show the pictures of custom lists instead of the words in the pdf word list
+        --clue-file <FILE>   Read clues from <FILE> with one \"word: clue\" pair per line, implies --clues
+        --subtitle <TEXT>    Instructions printed below the title of every puzzle
+        --message <TEXT>     Hide <TEXT> in the letters that are left over after all words are found
+        --answer-key         Add answer pages to pdf output
+        --language <LANG>    Language of the words found online: english or spanish (default: english)
+        --seed <NUMBER>      Makes the puzzles repeatable, the same seed and words give the same puzzles
+    -v, --verbose            Show details about the generation and write them to the log file
+    -c, --coordinates        Label the rows and columns of text grids
+    -a, --answers            Append answer grids to text output
+    -h, --help               Print this message
+
+Options that aren't given use the defaults from the settings of the graphical interface.";
+
+pub fn run(args: Vec<String>) -> i32 {
+    let options = match parse_args(args) {
+        Ok(Some(val)) => val,
+        Ok(None) => {
+            println!("{}", get_usage());
+            return 0;
+        },
+        Err(err) => {
+            eprintln!("Error: {}\n\n{}", err, get_usage());
+            return 2;
+        }
+    };
+
+    match generate(options) {
+        Ok(_) => 0,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            1
+        }
+    }
+}
+
+fn generate(options: CliOptions) -> Result<(), CliError> {
+    let mut requests: Vec<WordSearchRequest> = Vec::new();
+    for (stype, word) in &options.requests {
+        requests.push(WordSearchRequest {
+            word: word.clone(),
+            stype: *stype,
+            max_count: options.word_count as usize,
+            height: options.letter_count as usize,
+            width: options.letter_count as usize,
+            message: options.message.clone(),
+            kind: options.kind,
+            directions: options.directions,
+            title: None,
+            subtitle: options.subtitle.clone(),
+            language: options.language,
+            seed: options.seed
+        });
+    }
+
+    let (mut results, errors) = request::handle_requests(requests);
+    for (title, err) in errors {
+        eprintln!("Warning: {}: {}", title, err);
+    }
+    if results.is_empty() {
+        return Err(CliError(String::from("No word searches could be generated")));
+    }
+    if let Some(path) = &options.clue_file {
+        match word_list::load_clues(path) {
+            Ok(clues) => request::add_clues(&mut results, &clues),
+            Err(err) => return Err(CliError(format!("{}", err)))
+        }
+    }
+
+    let text_options = TextOptions {
+        coordinates: options.coordinates,
+        answers: options.answers,
+        markdown: options.format == "markdown"
+    };
+
+    let directory = match (options.format.as_str(), &options.output) {
+        ("text", None) | ("markdown", None) => {
+            print!("{}", text::render_all(&results, &text_options));
+            return Ok(());
+        },
+        (_, Some(dir)) => dir.clone(),
+        _ => return Err(CliError(format!("The {} format requires an output directory (--output)", options.format)))
+    };
+    let pdf_options = match options.format.as_str() {
+        "pdf" => Some(get_pdf_options(&options)?),
+        _ => None
+    };
+    let extension = match options.format.as_str() {
+        "text" => "txt",
+        "markdown" => "md",
+        format => format
+    };
+
+    let file_options = FileOptions {
+        directory,
+        template: options.file_name.clone(),
+        per_puzzle: options.per_puzzle,
+        overwrite: options.overwrite,
+        time: Local::now()
+    };
+    let files = match output::get_files(results, &file_options, extension) {
+        Ok(val) => val,
+        Err(err) => return Err(CliError(format!("{}", err)))
+    };
+    for (path, word_search_list) in files {
+        let result = match &pdf_options {
+            Some(pdf_options) => pdf::create_pdf(word_search_list, pdf_options, &path),
+            None if options.format == "html" => html::create_html(word_search_list, &path),
+            None => text::create_text(word_search_list, &path, &text_options)
+        };
+        if let Err(err) = result {
+            return Err(CliError(format!("{}: {}", path, err)));
+        }
+        println!("Wrote {}", path);
+    }
+
+    Ok(())
+}
+
+fn get_pdf_options(options: &CliOptions) -> Result<PdfOptions, CliError> {
+    let (width, height) = match options.page_size {
+        Some(val) => val,
+        None => pdf::get_format(&options.page_format)
+    };
+    let page = PageSetup::new(width, height, options.landscape, options.margins);
+    if !options.margins.fit(page.width, page.height) {
+        return Err(CliError(String::from("The margins are too large for the page")));
+    }
+
+    Ok(PdfOptions {
+        page,
+        layout: options.layout,
+        header: options.header.clone(),
+        footer: options.footer.clone(),
+        class_name: options.class_name.clone(),
+        title_font: fonts::load_font(&options.fonts.0),
+        grid_font: fonts::load_font(&options.fonts.1),
+        list_font: fonts::load_font(&options.fonts.2),
+        word_list: WordListOptions {
+            order: options.word_order,
+            checkboxes: options.checkboxes,
+            clues: options.clues,
+            pictures: options.pictures
+        },
+        answer_key: options.answer_key
+    })
+}
+
+fn parse_args(args: Vec<String>) -> Result<Option<CliOptions>, CliError> {
+    let prefs = match config::load_preferences(false) {
+        Ok(val) => val,
+        Err(err) => {
+            eprintln!("Warning: {}", err);
+            config::get_defaults()
+        }
+    };
+    logging::set_verbose(prefs.verbose_log);
+    let mut options = CliOptions {
+        requests: Vec::new(),
+        word_count: prefs.word_count,
+        letter_count: prefs.letter_count,
+        format: String::from("text"), //Text goes to stdout, so the output format of the settings isn't used
+        output: None,
+        page_size: get_custom_size(&prefs),
+        page_format: prefs.format,
+        landscape: prefs.landscape,
+        margins: Margins::parse(&prefs.margins).unwrap_or(Margins::uniform(pdf::mm_to_points(15.0))),
+        layout: prefs.layout,
+        header: prefs.header_template,
+        footer: prefs.footer_template,
+        class_name: prefs.class_name,
+        fonts: (prefs.title_font, prefs.grid_font, prefs.list_font),
+        word_order: prefs.word_order,
+        checkboxes: prefs.checkboxes,
+        clues: prefs.clues,
+        pictures: prefs.pictures,
+        clue_file: None,
+        message: None,
+        subtitle: None,
+        kind: prefs.kind,
+        directions: prefs.directions,
+        language: prefs.language,
+        seed: prefs.seed,
+        file_name: prefs.file_name,
+        per_puzzle: prefs.per_puzzle,
+        overwrite: prefs.if_exists == "Overwrite",
+        answer_key: prefs.answer_key,
+        coordinates: false,
+        answers: prefs.answer_key
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-c" | "--coordinates" => options.coordinates = true,
+            "-a" | "--answers" => options.answers = true,
+            "-v" | "--verbose" => {
+                logging::set_verbose(true);
+                logging::set_stderr_level(LevelFilter::Debug);
+            },
+            "-w" | "--words" => {
+                options.word_count = parse_count(&arg, args.next(), WORD_COUNT_RANGE)?;
+            },
+            "-l" | "--letters" => {
+                options.letter_count = parse_count(&arg, args.next(), LETTER_COUNT_RANGE)?;
+            },
+            "-f" | "--format" => {
+                let format = get_value(&arg, args.next())?.to_lowercase();
+                match format.as_str() {
+                    "text" | "markdown" | "html" | "pdf" => options.format = format,
+                    _ => return Err(CliError(format!("Unknown output format \"{}\"", format)))
+                }
+            },
+            "-o" | "--output" => {
+                options.output = Some(get_value(&arg, args.next())?);
+            },
+            "-n" | "--name" => options.file_name = get_value(&arg, args.next())?,
+            "--split" => options.per_puzzle = true,
+            "--overwrite" => options.overwrite = true,
+            "-p" | "--page" => {
+                let page_format = get_value(&arg, args.next())?;
+                match page_format.as_str() {
+                    "Letter" | "Half Letter" | "DINA4" | "DINA5" => {
+                        options.page_format = page_format;
+                        options.page_size = None;
+                    },
+                    _ => return Err(CliError(format!("Unknown page format \"{}\"", page_format)))
+                }
+            },
+            "-s" | "--size" => {
+                options.page_size = Some(parse_size(&get_value(&arg, args.next())?)?);
+            },
+            "-m" | "--margins" => {
+                let margins = get_value(&arg, args.next())?;
+                options.margins = match Margins::parse(&margins) {
+                    Some(val) => val,
+                    None => return Err(CliError(format!("\"{}\" are not valid margins", margins)))
+                };
+            },
+            "--landscape" => options.landscape = true,
+            "--header" => options.header = get_value(&arg, args.next())?,
+            "--footer" => options.footer = get_value(&arg, args.next())?,
+            "--class" => options.class_name = get_value(&arg, args.next())?,
+            "-L" | "--layout" => {
+                let layout = get_value(&arg, args.next())?.to_lowercase();
+                options.layout = match layout.as_str() {
+                    "1" => Layout::Single,
+                    "2" => Layout::TwoUp,
+                    "4" => Layout::FourUp,
+                    "booklet" => Layout::Booklet,
+                    _ => return Err(CliError(format!("Unknown layout \"{}\"", layout)))
+                };
+            },
+            "--sort" => {
+                let order = get_value(&arg, args.next())?.to_lowercase();
+                options.word_order = match order.as_str() {
+                    "added" => WordOrder::Insertion,
+                    "alphabetical" => WordOrder::Alphabetical,
+                    "length" => WordOrder::Length,
+                    _ => return Err(CliError(format!("Unknown word list order \"{}\"", order)))
+                };
+            },
+            "--checkboxes" => options.checkboxes = true,
+            "--clues" => options.clues = true,
+            "--pictures" => options.pictures = true,
+            "--message" => {
+                let message = get_value(&arg, args.next())?;
+                if word_search::get_message_letters(&message).is_empty() {
+                    return Err(CliError(format!("The hidden message \"{}\" has no letters", message)));
+                }
+                options.message = Some(message);
+            },
+            "--answer-key" => options.answer_key = true,
+            "--subtitle" => {
+                options.subtitle = Some(get_value(&arg, args.next())?).filter(|subtitle| subtitle.trim() != "");
+            },
+            "-k" | "--kind" => {
+                let kind = get_value(&arg, args.next())?.to_lowercase();
+                options.kind = match kind.as_str() {
+                    "wordsearch" => PuzzleKind::WordSearch,
+                    "fillin" => PuzzleKind::FillIn,
+                    "crisscross" => PuzzleKind::CrissCross,
+                    _ => return Err(CliError(format!("Unknown puzzle type \"{}\"", kind)))
+                };
+            },
+            "--language" => {
+                let language = get_value(&arg, args.next())?.to_lowercase();
+                options.language = match language.as_str() {
+                    "english" => Language::English,
+                    "spanish" => Language::Spanish,
+                    _ => return Err(CliError(format!("Unknown language \"{}\"", language)))
+                };
+            },
+            "--seed" => {
+                let seed = get_value(&arg, args.next())?;
+                options.seed = match seed.parse::<u64>() {
+                    Ok(val) => Some(val),
+                    Err(_) => return Err(CliError(format!("--seed expects a whole number, got \"{}\"", seed)))
+                };
+            },
+            "--clue-file" => {
+                options.clue_file = Some(get_value(&arg, args.next())?);
+                options.clues = true;
+            },
+            _ => {
+                if arg.starts_with("-") {
+                    return Err(CliError(format!("Unknown option \"{}\"", arg)));
+                }
+                options.requests.push(parse_request(&arg)?);
+            }
+        }
+    }
+
+    if options.requests.is_empty() {
+        return Err(CliError(String::from("No word searches have been requested")));
+    }
+    if options.kind != PuzzleKind::WordSearch {
+        if options.format == "html" {
+            return Err(CliError(String::from("Html output is only available for word searches")));
+        }
+        if options.message.is_some() {
+            return Err(CliError(String::from("Hidden messages are only available for word searches")));
+        }
+    }
+
+    Ok(Some(options))
+}
+
+fn parse_request(arg: &str) -> Result<(SearchType, String), CliError> {
+    let mut parts = arg.splitn(2, ':');
+    let stype = match parts.next().unwrap_or("").to_lowercase().as_str() {
+        "related" => SearchType::RelatedTo,
+        "rhymes" => SearchType::RhymesWith,
+        "ends" => SearchType::EndsWith,
+        "sounds" => SearchType::SoundsLike,
+        "before" => SearchType::BlankWord,
+        "follows" => SearchType::WordBlank,
+        "list" => SearchType::Custom,
+        _ => return Err(CliError(format!("\"{}\" is not a valid <TYPE>:<WORD> pair", arg)))
+    };
+    let word = match stype {
+        SearchType::Custom => parts.next().unwrap_or("").trim().to_string(), //File paths can contain spaces
+        _ => parts.next().unwrap_or("").replace(" ", "")
+    };
+    if word.is_empty() {
+        return Err(CliError(format!("The base word for \"{}\" is missing", arg)));
+    }
+
+    Ok((stype, word))
+}
+
+//The page size of the "Custom" format in the settings
+fn get_custom_size(prefs: &Preferences) -> Option<(f32, f32)> {
+    if prefs.format != "Custom" {
+        return None;
+    }
+
+    parse_size(&format!("{}x{}{}", prefs.custom_width.trim(), prefs.custom_height.trim(), prefs.size_unit)).ok()
+}
+
+fn parse_size(size: &str) -> Result<(f32, f32), CliError> {
+    let error = CliError(format!("\"{}\" is not a valid page size", size));
+    let (dimensions, inches) = if size.ends_with("in") {
+        (size.trim_end_matches("in"), true)
+    }
+    else {
+        (size.trim_end_matches("mm"), false)
+    };
+
+    let parts: Vec<&str> = dimensions.split('x').collect();
+    if parts.len() != 2 {
+        return Err(error);
+    }
+    match (parts[0].trim().parse::<f32>(), parts[1].trim().parse::<f32>()) {
+        (Ok(width), Ok(height)) if width > 0.0 && height > 0.0 => {
+            if inches {
+                Ok((pdf::in_to_points(width), pdf::in_to_points(height)))
+            }
+            else {
+                Ok((pdf::mm_to_points(width), pdf::mm_to_points(height)))
+            }
+        },
+        _ => Err(error)
+    }
+}
+
+fn parse_count(arg: &str, value: Option<String>, range: RangeInclusive<u8>) -> Result<u8, CliError> {
+    let value = get_value(arg, value)?;
+    match value.parse::<u8>() {
+        Ok(val) if range.contains(&val) => Ok(val),
+        _ => Err(CliError(format!("{} expects a number from {} to {}, got \"{}\"", arg, range.start(), range.end(), value)))
+    }
+}
+
+fn get_usage() -> String {
+    USAGE.replace("{words}", &format!("{} to {}", WORD_COUNT_RANGE.start(), WORD_COUNT_RANGE.end()))
+        .replace("{letters}", &format!("{} to {}", LETTER_COUNT_RANGE.start(), LETTER_COUNT_RANGE.end()))
+        .replace("{lists}", &assets::get_word_list_names().join(", "))
+}
+
+fn get_value(arg: &str, value: Option<String>) -> Result<String, CliError> {
+    match value {
+        Some(val) => Ok(val),
+        None => Err(CliError(format!("{} expects a value", arg)))
+    }
+}
+
+struct CliOptions {
+    requests: Vec<(SearchType, String)>,
+    word_count: u8,
+    letter_count: u8,
+    format: String,
+    output: Option<String>,
+    page_format: String,
+    page_size: Option<(f32, f32)>,
+    landscape: bool,
+    margins: Margins,
+    layout: Layout,
+    header: String,
+    footer: String,
+    class_name: String,
+    fonts: (String, String, String), //Title, grid and word list
+    word_order: WordOrder,
+    checkboxes: bool,
+    clues: bool,
+    pictures: bool,
+    clue_file: Option<String>,
+    message: Option<String>,
+    subtitle: Option<String>,
+    kind: PuzzleKind,
+    directions: Directions,
+    language: Language,
+    seed: Option<u64>,
+    file_name: String,
+    per_puzzle: bool,
+    overwrite: bool,
+    answer_key: bool,
+    coordinates: bool,
+    answers: bool
+}
+
+#[derive(Debug)]
+struct CliError(String);
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.0)
+    }
+}
\ No newline at end of file
@@ -1,55 +1,267 @@
 use std::fs::{ self, OpenOptions };
-use std::io::Write;
+use std::io::{ ErrorKind, Write };
+use std::path::{ Path, PathBuf };
+use std::ops::RangeInclusive;
 use serde::{ Serialize, Deserialize };
-use serde_json;
+use serde_json::{ self, Value };
+
+use crate::word_search::{ PuzzleKind, Directions };
+use crate::word_list::Language;
+use crate::pdf::{ Layout, WordOrder };
+use crate::assets;
+
+static APP_DIRECTORY: &str = "wordsearch";
+static FILE_NAME: &str = "preferences.json";
+static LEGACY_PATH: &str = "./data/preferences.json"; //Used before the config directory
+const VERSION: u64 = 1;
+
+//Limits shared by the sliders, the settings and the command line
+pub const WORD_COUNT_RANGE: RangeInclusive<u8> = 10..=20;
+pub const LETTER_COUNT_RANGE: RangeInclusive<u8> = 8..=14;
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct Preferences {
+    pub version: u64,
     pub theme: String,
     pub word_count: u8,
     pub letter_count: u8,
     pub format: String,
-    pub save_directory: String
+    pub save_directory: String,
+    pub header_template: String,
+    pub footer_template: String,
+    pub class_name: String,
+    pub title_font: String,
+    pub grid_font: String,
+    pub list_font: String,
+    pub large_text: bool,
+    pub high_contrast: bool,
+    pub verbose_log: bool, //Writes details about every generation to the log file
+    //Generation and rendering defaults
+    pub kind: PuzzleKind,
+    pub directions: Directions, //Used by new word searches
+    pub language: Language,
+    pub seed: Option<u64>,
+    pub custom_width: String, //Used by the "Custom" format
+    pub custom_height: String,
+    pub size_unit: String,
+    pub landscape: bool,
+    pub margins: String, //In mm, either one value or "top, right, bottom, left"
+    pub layout: Layout,
+    pub word_order: WordOrder,
+    pub checkboxes: bool,
+    pub clues: bool,
+    pub pictures: bool,
+    pub answer_key: bool,
+    pub check_words: bool,
+    pub output_format: String,
+    pub file_name: String,
+    pub per_puzzle: bool,
+    pub if_exists: String
 }
 
-pub fn get_preferences() -> Preferences {
-    let data = match fs::read_to_string("./data/preferences.json") {
+//The default settings file of the data directories over the one built into the program
+pub fn get_defaults() -> Preferences {
+    let data = assets::get_default_preferences();
+    match serde_json::from_str::<Value>(&data).and_then(|value| serde_json::from_value::<Preferences>(merge(get_builtin_defaults(), migrate(value)))) {
+        Ok(val) => val.limit(),
+        Err(err) => {
+            log::warn!("The default settings could not be read: {}", err);
+            Preferences::default().limit()
+        }
+    }
+}
+
+//A missing file gives the defaults. Files that can't be read are moved aside
+//so saving doesn't replace them when move_corrupt is set, and the reason is returned.
+//The command line never saves the settings, so it leaves the file where it is
+pub fn load_preferences(move_corrupt: bool) -> Result<Preferences, String> {
+    let path = get_path();
+    let read_path = if !path.exists() && Path::new(LEGACY_PATH).exists() {
+        PathBuf::from(LEGACY_PATH)
+    }
+    else {
+        path.clone()
+    };
+
+    let data = match fs::read_to_string(&read_path) {
         Ok(val) => val,
-        Err(_) => return Preferences::default()
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(get_defaults()),
+        Err(err) => return Err(format!("The settings file \"{}\" could not be read: {}", read_path.display(), err))
     };
 
-    match serde_json::from_str(&data) {
+    let value: Value = match serde_json::from_str(&data) {
         Ok(val) => val,
-        Err(_) => Preferences::default()
+        Err(err) => return Err(report(&read_path, err, move_corrupt))
+    };
+    let defaults = match serde_json::to_value(get_defaults()) {
+        Ok(val) => val,
+        Err(_) => get_builtin_defaults()
+    };
+    match serde_json::from_value::<Preferences>(merge(defaults, migrate(value))) {
+        Ok(val) => Ok(val.limit()),
+        Err(err) => Err(report(&read_path, err, move_corrupt))
     }
 }
 
 pub fn save_preferences(prefs: Preferences) -> bool {
+    let prefs = Preferences {
+        version: VERSION,
+        ..prefs
+    };
     let data = match serde_json::to_string_pretty(&prefs) {
         Ok(val) => val,
         Err(_) => return false
     };
 
-    let mut file = match OpenOptions::new().write(true).create(true).truncate(true).open("./data/preferences.json") {
+    let path = get_path();
+    if let Some(directory) = path.parent() {
+        if fs::create_dir_all(directory).is_err() {
+            return false;
+        }
+    }
+    let mut file = match OpenOptions::new().write(true).create(true).truncate(true).open(&path) {
         Ok(val) => val,
         Err(_) => return false
     };
     
-    match file.write_all(&data.as_bytes()) {
-        Ok(_) => true,
-        Err(_) => false
+    file.write_all(data.as_bytes()).is_ok()
+}
+
+//The platform config directory, e.g. ~/.config/wordsearch on Linux
+fn get_path() -> PathBuf {
+    match dirs::config_dir() {
+        Some(val) => val.join(APP_DIRECTORY).join(FILE_NAME),
+        None => PathBuf::from(LEGACY_PATH)
+    }
+}
+
+//Brings files of older versions up to date, missing fields are filled in by merge
+fn migrate(mut value: Value) -> Value {
+    let version = value.get("version").and_then(|version| version.as_u64()).unwrap_or(0);
+
+    if version < 1 { //Relative save directories were relative to the program, not the config directory
+        let save_directory = value.get("saveDirectory").and_then(|directory| directory.as_str()).map(|directory| directory.to_string());
+        if let (Some(directory), Ok(current)) = (save_directory, std::env::current_dir()) {
+            if Path::new(&directory).is_relative() {
+                let absolute = current.join(directory.trim_start_matches("./"));
+                value["saveDirectory"] = Value::String(absolute.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    if value.is_object() {
+        value["version"] = Value::from(VERSION);
+    }
+    value
+}
+
+//An empty save directory in the settings files stands for this one, e.g. ~/Documents/wordsearch
+fn get_save_directory() -> String {
+    let directory = match dirs::document_dir().or_else(dirs::home_dir) {
+        Some(val) => val.join(APP_DIRECTORY),
+        None => match std::env::current_dir() {
+            Ok(val) => val.join(APP_DIRECTORY),
+            Err(_) => PathBuf::from(APP_DIRECTORY)
+        }
+    };
+    directory.to_string_lossy().to_string()
+}
+
+//Every setting comes from the built in file, so the defaults are only written down once
+fn get_builtin_defaults() -> Value {
+    match serde_json::from_str(assets::get_builtin_preferences()) {
+        Ok(val) => val,
+        Err(_) => Value::Null
+    }
+}
+
+//Fields missing from the value are taken from the defaults
+fn merge(mut defaults: Value, value: Value) -> Value {
+    match (defaults.as_object_mut(), value) {
+        (Some(fields), Value::Object(values)) => {
+            for (key, field) in values {
+                fields.insert(key, field);
+            }
+            defaults
+        },
+        (_, value) => value
+    }
+}
+
+fn limit_count(count: u8, range: RangeInclusive<u8>) -> u8 {
+    count.max(*range.start()).min(*range.end())
+}
+
+fn report(path: &Path, err: serde_json::Error, move_corrupt: bool) -> String {
+    let corrupt_path = path.with_extension("corrupt.json");
+    if move_corrupt && fs::rename(path, &corrupt_path).is_ok() {
+        return format!("The settings file could not be read ({}), it was moved to \"{}\" and the default settings are used", err, corrupt_path.display());
+    }
+    format!("The settings file \"{}\" could not be read ({}), the default settings are used", path.display(), err)
+}
+
+impl Default for Preferences {
+    //The built in file is part of the program and checked by the tests, so it always parses
+    fn default() -> Preferences {
+        serde_json::from_value(get_builtin_defaults()).expect("The built in default settings are invalid")
     }
 }
 
 impl Preferences {
-    pub fn default() -> Preferences {
+    //Counts from edited files are kept in range instead of being rejected and empty fields get their defaults
+    fn limit(self) -> Preferences {
+        let defaults = Preferences::default();
         Preferences {
-            theme: String::from("Dark"),
-            word_count: 15,
-            letter_count: 11,
-            format: String::from("Letter"),
-            save_directory: String::from("./out")
+            word_count: limit_count(self.word_count, WORD_COUNT_RANGE),
+            letter_count: limit_count(self.letter_count, LETTER_COUNT_RANGE),
+            margins: if self.margins.trim() == "" { defaults.margins } else { self.margins },
+            file_name: if self.file_name.trim() == "" { defaults.file_name } else { self.file_name },
+            save_directory: if self.save_directory.trim() == "" { get_save_directory() } else { self.save_directory },
+            ..self
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_defaults_are_complete() {
+        let value = get_builtin_defaults();
+        assert!(serde_json::from_value::<Preferences>(value).is_ok());
+    }
+
+    #[test]
+    fn missing_fields_use_the_defaults() {
+        let value: Value = serde_json::from_str(r#"{ "version": 1, "theme": "Dark", "wordCount": 12 }"#).unwrap();
+        let prefs: Preferences = serde_json::from_value(merge(get_builtin_defaults(), value)).unwrap();
+        assert_eq!(prefs.theme, "Dark");
+        assert_eq!(prefs.word_count, 12);
+        assert_eq!(prefs.letter_count, Preferences::default().letter_count);
+    }
+
+    #[test]
+    fn empty_save_directory_is_absolute() {
+        let prefs = Preferences::default();
+        assert_eq!(prefs.save_directory, "");
+        assert!(Path::new(&prefs.limit().save_directory).is_absolute());
+    }
+
+    #[test]
+    fn migrate_makes_old_save_directories_absolute() {
+        let value: Value = serde_json::from_str(r#"{ "saveDirectory": "./out" }"#).unwrap();
+        let value = migrate(value);
+        assert_eq!(value["version"], Value::from(VERSION));
+        let directory = value["saveDirectory"].as_str().unwrap_or("");
+        assert!(Path::new(directory).is_absolute());
+        assert!(directory.ends_with("out"));
+    }
+
+    #[test]
+    fn migrate_keeps_current_files() {
+        let value: Value = serde_json::from_str(r#"{ "version": 1, "saveDirectory": "out" }"#).unwrap();
+        assert_eq!(migrate(value)["saveDirectory"], "out");
+    }
+}
@@ -0,0 +1,260 @@
+use std::fs;
+use std::io::Error;
+use std::sync::{ Arc, Mutex };
+use std::thread;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use pdf_canvas::{ BuiltinFont, FontSource, Canvas };
+use ttf_parser::{ Face, OutlineBuilder };
+
+use crate::assets;
+
+static FONT_DIRECTORY: &str = "fonts";
+
+lazy_static! {
+    static ref BUILTIN_FONTS: Vec<(String, BuiltinFont)> = vec![
+        (String::from("Times Roman"), BuiltinFont::Times_Roman),
+        (String::from("Times Bold"), BuiltinFont::Times_Bold),
+        (String::from("Times Italic"), BuiltinFont::Times_Italic),
+        (String::from("Times Bold Italic"), BuiltinFont::Times_BoldItalic),
+        (String::from("Helvetica"), BuiltinFont::Helvetica),
+        (String::from("Helvetica Bold"), BuiltinFont::Helvetica_Bold),
+        (String::from("Helvetica Oblique"), BuiltinFont::Helvetica_Oblique),
+        (String::from("Helvetica Bold Oblique"), BuiltinFont::Helvetica_BoldOblique),
+        (String::from("Courier"), BuiltinFont::Courier),
+        (String::from("Courier Bold"), BuiltinFont::Courier_Bold),
+        (String::from("Courier Oblique"), BuiltinFont::Courier_Oblique),
+        (String::from("Courier Bold Oblique"), BuiltinFont::Courier_BoldOblique)
+    ];
+
+    static ref LOADED_FONTS: Mutex<HashMap<String, Arc<TrueTypeFont>>> = { //Font files are only read and parsed once
+        Mutex::new(HashMap::new())
+    };
+}
+
+//Returns the built in pdf fonts followed by the TrueType/OpenType files in the fonts data directories
+pub fn get_font_names() -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (name, _) in &*BUILTIN_FONTS {
+        names.push(name.clone());
+    }
+
+    let mut files: Vec<String> = assets::list_files(FONT_DIRECTORY, &["ttf", "otf"]).into_iter()
+        .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+        .collect();
+    files.sort();
+    names.append(&mut files);
+
+    names
+}
+
+//Unknown fonts and font files that can't be read fall back to Times Roman
+pub fn load_font(name: &str) -> Font {
+    for (builtin_name, font) in &*BUILTIN_FONTS {
+        if name == builtin_name {
+            return Font::Builtin(*font);
+        }
+    }
+
+    match get_font_file(name) {
+        Some(val) => Font::TrueType(val),
+        None => Font::default()
+    }
+}
+
+//The lock is held while the file is read, so every font file is parsed and kept at most once
+fn get_font_file(name: &str) -> Option<Arc<TrueTypeFont>> {
+    let mut loaded_fonts = match LOADED_FONTS.lock() {
+        Ok(val) => val,
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            return get_font_file(name);
+        }
+    };
+
+    match loaded_fonts.entry(name.to_string()) {
+        Entry::Occupied(entry) => Some(entry.get().clone()),
+        Entry::Vacant(entry) => read_font_file(name).map(|font| entry.insert(Arc::new(font)).clone())
+    }
+}
+
+fn read_font_file(name: &str) -> Option<TrueTypeFont> {
+    let data = match assets::find_file(FONT_DIRECTORY, name).and_then(|path| fs::read(path).ok()) {
+        Some(val) => val,
+        None => {
+            log::warn!("The font \"{}\" could not be found, Times Roman is used instead", name);
+            return None;
+        }
+    };
+
+    //The parsed face borrows the file, which is kept like the loaded font until the program ends
+    let data: &'static [u8] = Box::leak(data.into_boxed_slice());
+    let face = match Face::parse(data, 0) {
+        Ok(val) => val,
+        Err(err) => {
+            log::warn!("The font \"{}\" could not be read: {}", name, err);
+            return None;
+        }
+    };
+    let units_per_em = face.units_per_em() as f32;
+
+    Some(
+        TrueTypeFont {
+            cap_height: face.capital_height().map(|height| height as f32).unwrap_or(units_per_em * 0.7),
+            face,
+            units_per_em
+        }
+    )
+}
+
+#[derive(Clone)]
+pub enum Font {
+    Builtin(BuiltinFont),
+    TrueType(Arc<TrueTypeFont>) //Drawn as glyph outlines, since pdf_canvas can only use the built in fonts
+}
+
+pub struct TrueTypeFont {
+    face: Face<'static>,
+    units_per_em: f32,
+    cap_height: f32
+}
+
+impl Default for Font {
+    fn default() -> Font {
+        Font::Builtin(BuiltinFont::Times_Roman)
+    }
+}
+
+impl Font {
+    pub fn get_width(&self, size: f32, text: &str) -> f32 {
+        match self {
+            Font::Builtin(font) => font.get_width(size, text),
+            Font::TrueType(font) => {
+                let mut width: f32 = 0.0;
+                for c in text.chars() {
+                    let glyph = font.face.glyph_index(c).unwrap_or(ttf_parser::GlyphId(0));
+                    width += font.face.glyph_hor_advance(glyph).unwrap_or(0) as f32;
+                }
+                width * size / font.units_per_em
+            }
+        }
+    }
+
+    //Height of capital letters, used for centering text vertically
+    pub fn get_cap_height(&self, size: f32) -> f32 {
+        match self {
+            Font::Builtin(font) => {
+                let ratio = match font {
+                    BuiltinFont::Courier | BuiltinFont::Courier_Bold | BuiltinFont::Courier_Oblique | BuiltinFont::Courier_BoldOblique => 0.562,
+                    BuiltinFont::Helvetica | BuiltinFont::Helvetica_Bold | BuiltinFont::Helvetica_Oblique | BuiltinFont::Helvetica_BoldOblique => 0.718,
+                    _ => 0.662
+                };
+                ratio * size
+            },
+            Font::TrueType(font) => font.cap_height * size / font.units_per_em
+        }
+    }
+
+    pub fn left_text(&self, canvas: &mut Canvas, x: f32, y: f32, size: f32, text: &str) -> Result<(), Error> {
+        match self {
+            Font::Builtin(font) => canvas.left_text(x, y, *font, size, text),
+            Font::TrueType(font) => draw_outlines(canvas, font, x, y, size, text)
+        }
+    }
+
+    pub fn center_text(&self, canvas: &mut Canvas, x: f32, y: f32, size: f32, text: &str) -> Result<(), Error> {
+        self.left_text(canvas, x - self.get_width(size, text) / 2.0, y, size, text)
+    }
+
+    pub fn right_text(&self, canvas: &mut Canvas, x: f32, y: f32, size: f32, text: &str) -> Result<(), Error> {
+        self.left_text(canvas, x - self.get_width(size, text), y, size, text)
+    }
+}
+
+fn draw_outlines(canvas: &mut Canvas, font: &TrueTypeFont, x: f32, y: f32, size: f32, text: &str) -> Result<(), Error> {
+    let face = &font.face;
+    let scale = size / font.units_per_em;
+
+    let mut builder = PathBuilder {
+        canvas,
+        x,
+        y,
+        scale,
+        last: (0.0, 0.0),
+        started: false,
+        result: Ok(())
+    };
+    for c in text.chars() {
+        let glyph = face.glyph_index(c).unwrap_or(ttf_parser::GlyphId(0));
+        face.outline_glyph(glyph, &mut builder);
+        builder.x += face.glyph_hor_advance(glyph).unwrap_or(0) as f32 * scale;
+    }
+
+    builder.result?;
+    if builder.started { //Whitespace has no outlines to fill
+        canvas.fill()?;
+    }
+
+    Ok(())
+}
+
+struct PathBuilder<'a, 'b> {
+    canvas: &'a mut Canvas<'b>,
+    x: f32,
+    y: f32,
+    scale: f32,
+    last: (f32, f32),
+    started: bool,
+    result: Result<(), Error>
+}
+
+impl<'a, 'b> PathBuilder<'a, 'b> {
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.x + x * self.scale, self.y + y * self.scale)
+    }
+}
+
+impl<'a, 'b> OutlineBuilder for PathBuilder<'a, 'b> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        if self.result.is_ok() {
+            self.result = self.canvas.move_to(x, y);
+        }
+        self.last = (x, y);
+        self.started = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        if self.result.is_ok() {
+            self.result = self.canvas.line_to(x, y);
+        }
+        self.last = (x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) { //Pdf only has cubic curves
+        let (x1, y1) = self.point(x1, y1);
+        let (x, y) = self.point(x, y);
+        let (x0, y0) = self.last;
+        if self.result.is_ok() {
+            self.result = self.canvas.curve_to(
+                x0 + (x1 - x0) * 2.0 / 3.0, y0 + (y1 - y0) * 2.0 / 3.0,
+                x + (x1 - x) * 2.0 / 3.0, y + (y1 - y) * 2.0 / 3.0,
+                x, y
+            );
+        }
+        self.last = (x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let (x, y) = self.point(x, y);
+        if self.result.is_ok() {
+            self.result = self.canvas.curve_to(x1, y1, x2, y2, x, y);
+        }
+        self.last = (x, y);
+    }
+
+    fn close(&mut self) {} //Filling closes every sub path
+}
@@ -0,0 +1,48 @@
+use std::process::Command;
+use std::thread;
+use iced::futures::channel::oneshot;
+
+//Native dialogs block until the user closes them, so they run on their own thread while the window keeps working.
+//The picked path is None when the dialog is cancelled
+
+pub async fn pick_folder(title: &'static str, start: String) -> Option<String> {
+    run(move || tinyfiledialogs::select_folder_dialog(title, &start)).await
+}
+
+pub async fn pick_file(title: &'static str, start: String, patterns: &'static [&'static str], description: &'static str) -> Option<String> {
+    run(move || tinyfiledialogs::open_file_dialog(title, &start, Some((patterns, description)))).await
+}
+
+pub async fn pick_save_file(title: &'static str, start: String, patterns: &'static [&'static str], description: &'static str) -> Option<String> {
+    run(move || tinyfiledialogs::save_file_dialog_with_filter(title, &start, patterns, description)).await
+}
+
+async fn run<F: FnOnce() -> Option<String> + Send + 'static>(dialog: F) -> Option<String> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let _ = sender.send(dialog());
+    });
+
+    receiver.await.unwrap_or(None)
+}
+
+//Opens a file or folder with the program the system uses for it
+pub fn open_path(path: &str) -> bool {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    }
+    else if cfg!(target_os = "macos") {
+        "open"
+    }
+    else {
+        "xdg-open"
+    };
+
+    match Command::new(program).arg(path).spawn() {
+        Ok(mut child) => {
+            thread::spawn(move || child.wait()); //Don't leave the finished process behind
+            true
+        },
+        Err(_) => false
+    }
+}
@@ -1,33 +1,53 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use std::thread;
+use std::path::Path;
+use std::time::{ Duration, Instant };
+use std::ops::RangeInclusive;
 use iced::{ 
     Application, Column, Text, Settings, Element, Container, Length, Rule, Row,
-    Align, Space, ProgressBar, Command, Subscription, HorizontalAlignment
+    Align, Space, ProgressBar, Command, Subscription, HorizontalAlignment, Color
 };
 use iced::widget::{
     slider::{ self, Slider }, 
     pick_list::{ self, PickList }, 
     text_input::{ self, TextInput },
     scrollable::{ self, Scrollable },
-    button::{ self, Button }
+    button::{ self, Button },
+    Checkbox,
+    Canvas
 };
 use iced::window;
 use iced::time;
+use iced::keyboard::{ KeyCode, Modifiers };
+use iced_native::{ self, Event };
 use iced::executor;
-use iced;
-
-use crate::word_list::SearchType;
-use crate::request::{ self, WordSearchRequest };
-use crate::config::{ self, Preferences };
-use crate::pdf;
+use chrono::{ DateTime, Local };
+
+use crate::word_list::{ self, SearchType, Language };
+use crate::request::{ self, WordSearchRequest, TitledWordSearch };
+use crate::config::{ self, Preferences, WORD_COUNT_RANGE, LETTER_COUNT_RANGE };
+use crate::pdf::{ self, Layout, PdfOptions, PageSetup, Margins, WordListOptions, WordOrder };
+use crate::html;
+use crate::text::{ self, TextOptions };
 use crate::img;
+use crate::fonts;
+use crate::word_search::{ PuzzleKind, Directions };
+use crate::project::{ self, Project, ProjectEntry };
+use crate::output::{ self, FileOptions };
+use crate::assets;
+use crate::logging;
 
 mod styling;
 use styling::Theme;
+mod preview;
+use preview::GridPreview;
+mod dialog;
 
 const TITLE_SIZE: u16 = 40;
 const TITLE_2_SIZE: u16 = 30;
+const ERROR_COLOR: Color = Color::from_rgb(0.8, 0.0, 0.0);
 
 lazy_static! {
     static ref ALL_GEN_TYPES: Vec<String> = vec![
@@ -36,26 +56,78 @@ lazy_static! {
         String::from("Ends with [Letters]"),
         String::from("Sounds like [Word]"),
         String::from("Comes before [Word]"),
-        String::from("Follows [Word]")
+        String::from("Follows [Word]"),
+        String::from("Custom List [File]")
     ];
 
     static ref ALL_FORMATS: Vec<String> = vec![
         String::from("Letter"),
         String::from("Half Letter"),
         String::from("DINA4"),
-        String::from("DINA5")
+        String::from("DINA5"),
+        String::from("Custom")
+    ];
+
+    static ref ALL_ORIENTATIONS: Vec<String> = vec![
+        String::from("Portrait"),
+        String::from("Landscape")
+    ];
+
+    static ref ALL_UNITS: Vec<String> = vec![
+        String::from("mm"),
+        String::from("in")
+    ];
+
+    static ref ALL_LAYOUTS: Vec<String> = vec![
+        String::from("One per Page"),
+        String::from("Two per Page"),
+        String::from("Four per Page"),
+        String::from("Booklet")
+    ];
+
+    static ref ALL_KINDS: Vec<String> = vec![
+        String::from("Word Search"),
+        String::from("Fill-In"),
+        String::from("Criss-Cross")
+    ];
+
+    static ref ALL_WORD_ORDERS: Vec<String> = vec![
+        String::from("In Order Added"),
+        String::from("Alphabetical"),
+        String::from("By Length")
+    ];
+
+    static ref ALL_LANGUAGES: Vec<String> = vec![
+        String::from("English"),
+        String::from("Spanish")
+    ];
+
+    static ref ALL_OUTPUTS: Vec<String> = vec![
+        String::from("PDF"),
+        String::from("HTML"),
+        String::from("Text"),
+        String::from("Markdown")
     ];
 
-    static ref ALL_THEMES: Vec<String> = vec![
-        String::from("Light"),
-        String::from("Dark")
+    static ref ALL_CONFLICT_MODES: Vec<String> = vec![
+        String::from("Ask"),
+        String::from("Add a Number"),
+        String::from("Overwrite")
     ];
 
     static ref GEN_STATUS: Mutex<GenStatus> = {
         Mutex::new(GenStatus::InProgress)
     };
 
-    static ref ERR_MSGS: Mutex<Vec<String>> = {
+    static ref RESULTS: Mutex<Vec<PuzzleResult>> = {
+        Mutex::new(Vec::new())
+    };
+
+    static ref PREVIEW: Mutex<Vec<TitledWordSearch>> = {
+        Mutex::new(Vec::new())
+    };
+
+    static ref WORD_CHECKS: Mutex<Vec<(usize, String, String)>> = { //Row index, checked word and warning
         Mutex::new(Vec::new())
     };
 }
@@ -77,6 +149,7 @@ pub fn run() -> iced::Result {
 enum ProgressState {
     Creating,
     Generating,
+    Previewing,
     Finished,
     ChangingSettings,
 }
@@ -89,16 +162,84 @@ struct Gui {
     word_count: u8,
     page_format_pl: pick_list::State<String>,
     page_format: &'static String,
+    custom_width_in: text_input::State,
+    custom_width: String,
+    custom_height_in: text_input::State,
+    custom_height: String,
+    size_unit_pl: pick_list::State<String>,
+    size_unit: &'static String,
+    orientation_pl: pick_list::State<String>,
+    orientation: &'static String,
+    margins_in: text_input::State,
+    margins: String,
+    class_name_in: text_input::State,
+    class_name: String,
+    header_template: String,
+    footer_template: String,
+    title_font: String,
+    grid_font: String,
+    list_font: String,
+    page_layout_pl: pick_list::State<String>,
+    page_layout: &'static String,
+    kind_pl: pick_list::State<String>,
+    kind: &'static String,
+    word_order_pl: pick_list::State<String>,
+    word_order: &'static String,
+    language_pl: pick_list::State<String>,
+    language: &'static String,
+    seed_in: text_input::State,
+    seed: String, //Empty for random puzzles
+    directions: Directions, //Used by new word searches
+    checkboxes: bool,
+    clues: bool,
+    pictures: bool,
+    check_words: bool,
+    clue_file_in: text_input::State,
+    clue_file: String,
+    answer_key: bool,
+    output_format_pl: pick_list::State<String>,
+    output_format: &'static String,
     save_dir_in: text_input::State,
     save_dir: String,
+    file_name_in: text_input::State,
+    file_name: String,
+    per_puzzle: bool,
+    if_exists_pl: pick_list::State<String>,
+    if_exists: &'static String,
+    project_path_in: text_input::State,
+    project_path: String,
+    save_project_button: button::State,
+    load_project_button: button::State,
+    browse_project_button: button::State,
+    browse_save_dir_button: button::State,
+    browse_clue_file_button: button::State,
     gen_button: button::State,
+    settings_scroll: scrollable::State,
     go_to_settings_button: button::State,
     //Word Search Fields
+    word_list_hint: String, //Names of the built in word lists
     word_search_list_scroll: scrollable::State,
     word_search_list: Vec<WordSearchField>,
     //Generation State
     progress_state: ProgressState,
     finished_button: button::State,
+    finished_list: Vec<FinishedItem>,
+    finished_dir: String, //Directory of the last export
+    finished_scroll: scrollable::State,
+    open_folder_button: button::State,
+    generate_again_button: button::State,
+    //Preview
+    preview_list: Vec<PreviewItem>,
+    preview_clues: HashMap<String, String>,
+    preview_scroll: scrollable::State,
+    preview_back_button: button::State,
+    accept_all_button: button::State,
+    export_button: button::State,
+    conflicts: Vec<String>, //Existing files the export would replace
+    export_time: DateTime<Local>, //Used for the file names of the current export
+    overwrite_button: button::State,
+    rename_button: button::State,
+    cancel_button: button::State,
     //Errors
     err_msg: String,
     err: bool,
@@ -106,7 +247,16 @@ struct Gui {
     theme: Theme,
     //Settings
     theme_sett_pl: pick_list::State<String>,
-    theme_sett: &'static String,
+    theme_sett: String,
+    theme_names: Vec<String>,
+    theme_name: String, //Theme that is shown, changes while looking at the settings
+    high_contrast: bool,
+    system_dark: bool,
+    large_text: bool,
+    large_text_sett: bool,
+    high_contrast_sett: bool,
+    verbose_log_sett: bool,
+    open_log_button: button::State,
     word_count_sett_in: text_input::State,
     word_count_sett: String,
     letter_count_sett_in: text_input::State,
@@ -115,6 +265,22 @@ struct Gui {
     page_format_sett: &'static String,
     save_dir_sett_in: text_input::State,
     save_dir_sett: String,
+    browse_save_dir_sett_button: button::State,
+    header_sett_in: text_input::State,
+    header_sett: String,
+    footer_sett_in: text_input::State,
+    footer_sett: String,
+    class_name_sett_in: text_input::State,
+    class_name_sett: String,
+    font_names: Vec<String>,
+    title_font_sett_pl: pick_list::State<String>,
+    title_font_sett: String,
+    grid_font_sett_pl: pick_list::State<String>,
+    grid_font_sett: String,
+    list_font_sett_pl: pick_list::State<String>,
+    list_font_sett: String,
+    directions_sett: Directions,
+    settings_page_scroll: scrollable::State,
     save_sett_button: button::State,
     return_button: button::State
 }
@@ -123,39 +289,316 @@ struct Gui {
 struct WordSearchField {
     base_word_in: text_input::State,
     base_word: String,
+    browse_button: button::State, //Only shown for custom lists
     search_type_pl: pick_list::State<String>,
     search_type: &'static String,
+    message_in: text_input::State,
+    message: String,
+    //Overrides of the general settings, empty fields use them
+    title_in: text_input::State,
+    title: String,
+    subtitle_in: text_input::State,
+    subtitle: String,
+    letter_count_in: text_input::State,
+    letter_count: String,
+    word_count_in: text_input::State,
+    word_count: String,
+    directions: Directions,
+    err_msg: String, //Shown in red below the row
+    warning: String, //Too few words found online
+    checked: String, //Type and word of the last online check
+    last_edit: Instant,
+    options_button: button::State,
+    show_options: bool,
+    remove_button: button::State,
+    duplicate_button: button::State,
+    up_button: button::State,
+    down_button: button::State,
     new_button: button::State,
     field_type: WordSearchFieldType,
     index: usize
 }
 
+struct PreviewItem {
+    word_search: TitledWordSearch,
+    alternatives: Vec<String>, //Fetched words that aren't in the word search
+    words: Vec<PreviewWord>,
+    accepted: bool,
+    err_msg: String,
+    regenerate_button: button::State
+}
+
+//What happened to one word search, shown when everything is finished
+#[derive(Clone)]
+struct PuzzleResult {
+    title: String,
+    path: Option<String>, //File the word search was written to
+    err_msg: Option<String>
+}
+
+struct FinishedItem {
+    result: PuzzleResult,
+    open_button: button::State
+}
+
+struct PreviewWord {
+    word: String,
+    remove_button: button::State,
+    swap_pl: pick_list::State<String>
+}
+
+impl WordSearchField {
+    fn new(index: usize) -> WordSearchField {
+        WordSearchField {
+            base_word_in: text_input::State::new(),
+            base_word: String::from(""),
+            browse_button: button::State::new(),
+            search_type_pl: pick_list::State::default(),
+            search_type: &ALL_GEN_TYPES[0],
+            message_in: text_input::State::new(),
+            message: String::from(""),
+            title_in: text_input::State::new(),
+            title: String::from(""),
+            subtitle_in: text_input::State::new(),
+            subtitle: String::from(""),
+            letter_count_in: text_input::State::new(),
+            letter_count: String::from(""),
+            word_count_in: text_input::State::new(),
+            word_count: String::from(""),
+            directions: Directions::all(),
+            err_msg: String::from(""),
+            warning: String::from(""),
+            checked: String::from(""),
+            last_edit: Instant::now(),
+            options_button: button::State::new(),
+            show_options: false,
+            remove_button: button::State::new(),
+            duplicate_button: button::State::new(),
+            up_button: button::State::new(),
+            down_button: button::State::new(),
+            new_button: button::State::new(),
+            field_type: WordSearchFieldType::New,
+            index
+        }
+    }
+
+    fn is_focused(&self) -> bool {
+        self.base_word_in.is_focused() || self.message_in.is_focused() || self.title_in.is_focused()
+            || self.subtitle_in.is_focused() || self.letter_count_in.is_focused() || self.word_count_in.is_focused()
+    }
+
+    //Same settings without sharing the state of the widgets
+    fn duplicate(&self) -> WordSearchField {
+        let mut field = WordSearchField::new(self.index + 1);
+        field.field_type = WordSearchFieldType::Input;
+        field.base_word = self.base_word.clone();
+        field.search_type = self.search_type;
+        field.message = self.message.clone();
+        field.title = self.title.clone();
+        field.subtitle = self.subtitle.clone();
+        field.letter_count = self.letter_count.clone();
+        field.word_count = self.word_count.clone();
+        field.directions = self.directions;
+        field.show_options = self.show_options;
+        field.err_msg = self.err_msg.clone();
+        field.warning = self.warning.clone();
+        field.checked = self.checked.clone();
+        field
+    }
+
+    //Returns the letter and word count to use or what has to be fixed
+    fn validate(&self, default_letters: u8, default_words: u8) -> Result<(usize, usize), String> {
+        validate_base_word(&self.base_word, get_search_type(self.search_type))?;
+        let letter_count = match parse_override(&self.letter_count, default_letters, LETTER_COUNT_RANGE) {
+            Some(val) => val,
+            None => return Err(format!("The Letters per Row Must Be a Number From {}", get_range_text(LETTER_COUNT_RANGE)))
+        };
+        let word_count = match parse_override(&self.word_count, default_words, WORD_COUNT_RANGE) {
+            Some(val) => val,
+            None => return Err(format!("The Word Count Must Be a Number From {}", get_range_text(WORD_COUNT_RANGE)))
+        };
+        if !self.directions.horizontal && !self.directions.vertical && !self.directions.diagonal {
+            return Err(String::from("At Least One Direction Is Needed"));
+        }
+
+        Ok((letter_count as usize, word_count as usize))
+    }
+
+    fn get_request(&self, letter_count: usize, word_count: usize, kind: PuzzleKind, language: Language, seed: Option<u64>) -> WordSearchRequest {
+        let stype = get_search_type(self.search_type);
+        let word = match stype {
+            SearchType::Custom => self.base_word.trim().to_string(), //File paths can contain spaces
+            _ => self.base_word.replace(" ", "")
+        };
+
+        WordSearchRequest {
+            word,
+            stype,
+            max_count: word_count,
+            height: letter_count,
+            width: letter_count,
+            message: Some(self.message.trim().to_string()).filter(|message| !message.is_empty() && kind == PuzzleKind::WordSearch),
+            kind,
+            directions: self.directions,
+            title: Some(self.title.trim().to_string()).filter(|title| !title.is_empty()),
+            subtitle: Some(self.subtitle.trim().to_string()).filter(|subtitle| !subtitle.is_empty()),
+            language,
+            seed
+        }
+    }
+
+    //Messages are checked again once the row changes
+    fn edited(&mut self) {
+        self.last_edit = Instant::now();
+        self.warning = String::new();
+        if !self.err_msg.is_empty() {
+            self.err_msg = match validate_base_word(&self.base_word, get_search_type(self.search_type)) {
+                Ok(_) => String::new(),
+                Err(err) => err
+            };
+        }
+    }
+
+    fn get_check_key(&self) -> String {
+        format!("{}:{}", self.search_type, self.base_word.trim())
+    }
+}
+
+impl PreviewItem {
+    fn new(word_search: TitledWordSearch, accepted: bool) -> PreviewItem {
+        let max_length = word_search.request.width.max(word_search.request.height);
+        let mut alternatives: Vec<String> = Vec::new();
+        for word in &word_search.words {
+            let word = word.word.to_uppercase();
+            if word.chars().count() <= max_length && !word_search.word_search.word_list.contains(&word) && !alternatives.contains(&word) {
+                alternatives.push(word);
+            }
+        }
+        alternatives.sort();
+
+        let mut words: Vec<PreviewWord> = Vec::new();
+        for word in &word_search.word_search.word_list {
+            words.push(PreviewWord {
+                word: word.clone(),
+                remove_button: button::State::new(),
+                swap_pl: pick_list::State::default()
+            });
+        }
+
+        PreviewItem {
+            word_search,
+            alternatives,
+            words,
+            accepted,
+            err_msg: String::new(),
+            regenerate_button: button::State::new()
+        }
+    }
+}
+
 #[derive(Clone)]
 enum WordSearchFieldType {
     New,
     Input
 }
 
+//Where the path picked in a dialog goes
+#[derive(Debug, Clone, Copy)]
+enum PathTarget {
+    Project,
+    SaveDir,
+    SettingsSaveDir,
+    ClueFile,
+    WordList(usize),
+    SaveProject //Saving continues once the file is picked
+}
+
 #[derive(Debug, Clone)]
 enum Message {
     StartGenerate,
+    Export,
     AddWordSearch,
+    RemoveWordSearch(usize),
+    DuplicateWordSearch(usize),
+    MoveWordSearchUp(usize),
+    MoveWordSearchDown(usize),
     WordSearchFieldString((usize, String)),
     WordSearchFieldPickList((usize, String)),
+    WordSearchFieldHidden((usize, String)),
+    WordSearchFieldTitle((usize, String)),
+    WordSearchFieldSubtitle((usize, String)),
+    WordSearchFieldLetters((usize, String)),
+    WordSearchFieldWords((usize, String)),
+    WordSearchFieldDirections((usize, Directions)),
+    WordSearchFieldOptions(usize),
     Letter(u8),
     WordNum(u8),
     PageFormat(String),
+    CustomWidth(String),
+    CustomHeight(String),
+    SizeUnit(String),
+    Orientation(String),
+    Margins(String),
+    ClassName(String),
+    PageLayout(String),
+    PuzzleKind(String),
+    WordOrder(String),
+    Language(String),
+    Seed(String),
+    Checkboxes(bool),
+    Clues(bool),
+    Pictures(bool),
+    CheckWords(bool),
+    ClueFile(String),
+    AnswerKey(bool),
+    OutputFormat(String),
     SaveDir(String),
+    FileName(String),
+    PerPuzzle(bool),
+    IfExists(String),
+    ConflictOverwrite,
+    ConflictRename,
+    ConflictCancel,
+    ProjectPath(String),
+    BrowseProject,
+    BrowseSaveDir,
+    BrowseSettingsSaveDir,
+    BrowseClueFile,
+    BrowseWordList(usize),
+    PathPicked((PathTarget, Option<String>)),
+    OpenFile(usize),
+    OpenFolder,
+    SaveProject,
+    LoadProject,
+    PreviewRegenerate(usize),
+    PreviewRemoveWord((usize, String)),
+    PreviewSwapWord((usize, (String, String))),
+    PreviewAccept((usize, bool)),
+    PreviewAcceptAll,
+    ReturnFromPreview,
     Reset,
     Refresh,
     SaveSettings,
     GotoSettings,
     ReturnFromSettings,
     SettingsTheme(String),
+    SettingsLargeText(bool),
+    SettingsHighContrast(bool),
+    SettingsVerboseLog(bool),
+    OpenLogFolder,
+    KeyPressed((KeyCode, Modifiers)),
+    CheckSystemTheme,
     SettingsLetter(String),
     SettingsWordNum(String),
     SettingsPageFormat(String),
     SettingsSaveDir(String),
+    SettingsHeader(String),
+    SettingsFooter(String),
+    SettingsClassName(String),
+    SettingsTitleFont(String),
+    SettingsGridFont(String),
+    SettingsListFont(String),
+    SettingsDirections(Directions),
 }
 
 impl Application for Gui {
@@ -164,7 +607,15 @@ impl Application for Gui {
     type Flags = ();
 
     fn new(_flags: ()) -> (Self, Command<Message>) {
-        let gui = Gui::new_from_prefs(config::get_preferences());
+        let gui = match config::load_preferences(true) {
+            Ok(val) => Gui::new_from_prefs(val),
+            Err(err) => { //Tell why the settings are gone
+                let mut gui = Gui::new_from_prefs(config::get_defaults());
+                gui.err_msg = format!("Error: {}", err);
+                gui.err = true;
+                gui
+            }
+        };
 
         (gui, Command::none())
     }
@@ -179,83 +630,211 @@ impl Application for Gui {
                 let len = self.word_search_list.len();
                 let last_index = self.word_search_list[len - 1].index;
                 self.word_search_list[len - 1].field_type = WordSearchFieldType::Input;
+                self.word_search_list[len - 1].directions = self.directions;
 
-                self.word_search_list.push(WordSearchField {
-                    base_word_in: text_input::State::new(),
-                    base_word: String::from(""),
-                    search_type_pl: pick_list::State::default(),
-                    search_type: &ALL_GEN_TYPES[0],
-                    new_button: button::State::new(),
-                    field_type: WordSearchFieldType::New,
-                    index: last_index + 1
-                });
+                self.word_search_list.push(WordSearchField::new(last_index + 1));
+            },
+            Message::RemoveWordSearch(index) => {
+                self.word_search_list.remove(index);
+                self.reindex();
+            },
+            Message::DuplicateWordSearch(index) => {
+                let copy = self.word_search_list[index].duplicate();
+                self.word_search_list.insert(index + 1, copy);
+                self.reindex();
+            },
+            Message::MoveWordSearchUp(index) => {
+                if index > 0 {
+                    self.word_search_list.swap(index - 1, index);
+                    self.reindex();
+                }
+            },
+            Message::MoveWordSearchDown(index) => {
+                if index + 2 < self.word_search_list.len() { //The last row only adds new word searches
+                    self.word_search_list.swap(index, index + 1);
+                    self.reindex();
+                }
             },
             Message::StartGenerate => {
-                for i in 0..(self.word_search_list.len() - 1) {
-                    if self.word_search_list[i].base_word == String::from("") {
-                        self.err_msg = format!("Error: The Base Word for Word Search {} is Missing", i + 1);
+                let seed = match parse_seed(&self.seed) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        self.err_msg = err;
                         self.err = true;
                         return Command::none();
                     }
+                };
+                let language = get_language(self.language);
+                let kind = get_puzzle_kind(self.kind);
+
+                let mut requests: Vec<WordSearchRequest> = Vec::new();
+                let mut invalid = 0;
+                for item in self.word_search_list.iter_mut() {
+                    if let WordSearchFieldType::Input = item.field_type {
+                        match item.validate(self.letter_count, self.word_count) {
+                            Ok((letter_count, word_count)) => {
+                                item.err_msg = String::new();
+                                requests.push(item.get_request(letter_count, word_count, kind, language, seed));
+                            },
+                            Err(err) => {
+                                item.err_msg = err;
+                                invalid += 1;
+                            }
+                        }
+                    }
+                }
+                if invalid > 0 {
+                    self.err_msg = format!("Error: {} of the Word Searches Need to Be Changed, See the Messages in Red", invalid);
+                    self.err = true;
+                    return Command::none();
+                }
+                if requests.is_empty() {
+                    self.err_msg = "Error: No Word Searches Have Been Created".to_string();
+                    self.err = true;
+                    return Command::none();
+                }
+                if let Err(err) = output::check_directory(&self.save_dir) {
+                    self.err_msg = format!("Error: {}", err);
+                    self.err = true;
+                    return Command::none();
                 }
-                if self.word_search_list.len() == 1 {
-                    self.err_msg = format!("Error: No Word Searches Have Been Created");
+                if let Err(err) = self.get_page_setup() {
+                    self.err_msg = err;
                     self.err = true;
                     return Command::none();
                 }
-                if self.save_dir == "" {
-                    self.err_msg = format!("Error: No Saving Directory Has Been Specified");
+                if kind != PuzzleKind::WordSearch && self.output_format == "HTML" {
+                    self.err_msg = "Error: HTML Output is Only Available for Word Searches".to_string();
                     self.err = true;
                     return Command::none();
                 }
+                self.preview_clues = match (self.clues, self.clue_file.trim()) {
+                    (true, path) if !path.is_empty() => match word_list::load_clues(path) {
+                        Ok(val) => val,
+                        Err(err) => {
+                            self.err_msg = format!("Error: {}", err);
+                            self.err = true;
+                            return Command::none();
+                        }
+                    },
+                    _ => HashMap::new()
+                };
 
                 self.progress_state = ProgressState::Generating;
                 
-                //Spawn generation thread
-                let word_search_list = self.word_search_list.clone();
-                let format = self.page_format.clone();
-                let save_dir = self.save_dir.clone();
-                let max_count = self.word_count as usize;
-                let height = self.letter_count as usize;
-                let width = self.letter_count as usize;
+                //Spawn generation thread, the results are shown in the preview before they are exported
+                let clues = self.preview_clues.clone();
                 thread::spawn(move || {
-                    let mut requests: Vec<WordSearchRequest> = Vec::new();
-                    for word_search in word_search_list {
-                        if let WordSearchFieldType::Input = word_search.field_type {
-                            let request = WordSearchRequest {
-                                word: word_search.base_word.replace(" ", ""),
-                                stype: get_search_type(word_search.search_type),
-                                max_count: max_count,
-                                height: height,
-                                width: width
-                            };
-
-                            requests.push(request);
-                        }
-                    }
-
-                    let (results, errors) = request::handle_requests(requests);
-                    set_err_msgs(errors);
-
-                    let (width, height) = get_format(&format);
-                    match pdf::create_pdf(results, width, height, &save_dir) {
-                        Ok(val) => val,
-                        Err(_) => ()
-                    };
-
-                    set_gen_status(GenStatus::Done);
+                    let (mut results, errors) = request::handle_requests(requests);
+                    request::add_clues(&mut results, &clues);
+                    set_results(errors.into_iter().map(|(title, err)| PuzzleResult {
+                        title,
+                        path: None,
+                        err_msg: Some(err)
+                    }).collect());
+                    set_preview(results);
+
+                    set_gen_status(GenStatus::Generated);
                 });
 
             },
+            Message::Export => {
+                self.export_time = Local::now();
+                let file_options = self.get_file_options();
+                if self.if_exists == "Ask" {
+                    let titles: Vec<String> = self.preview_list.iter()
+                        .filter(|item| item.accepted)
+                        .map(|item| item.word_search.title.clone())
+                        .collect();
+                    let conflicts = output::get_conflicts(&titles, &file_options, get_extension(self.output_format));
+                    if !conflicts.is_empty() { //Wait for the user to decide
+                        self.conflicts = conflicts;
+                        return Command::none();
+                    }
+                }
+
+                self.export(file_options);
+            },
+            Message::ConflictOverwrite => {
+                self.conflicts = Vec::new();
+                let file_options = FileOptions {
+                    overwrite: true,
+                    ..self.get_file_options()
+                };
+                self.export(file_options);
+            },
+            Message::ConflictRename => {
+                self.conflicts = Vec::new();
+                let file_options = FileOptions {
+                    overwrite: false,
+                    ..self.get_file_options()
+                };
+                self.export(file_options);
+            },
+            Message::ConflictCancel => {
+                self.conflicts = Vec::new();
+            },
+            Message::PreviewRegenerate(index) => {
+                let word_list: Vec<String> = self.preview_list[index].word_search.words.iter().map(|word| word.word.clone()).collect();
+                self.regenerate_preview(index, word_list, None);
+            },
+            Message::PreviewRemoveWord((index, word)) => {
+                let word_list: Vec<String> = self.preview_list[index].word_search.word_search.word_list.iter()
+                    .filter(|item| **item != word)
+                    .cloned()
+                    .collect();
+                self.regenerate_preview(index, word_list, None);
+            },
+            Message::PreviewSwapWord((index, (old, new))) => {
+                let word_list: Vec<String> = self.preview_list[index].word_search.word_search.word_list.iter()
+                    .map(|item| if *item == old { new.clone() } else { item.clone() })
+                    .collect();
+                self.regenerate_preview(index, word_list, Some(new));
+            },
+            Message::PreviewAccept((index, val)) => {
+                self.preview_list[index].accepted = val;
+            },
+            Message::PreviewAcceptAll => {
+                for item in &mut self.preview_list {
+                    item.accepted = true;
+                }
+            },
+            Message::ReturnFromPreview => {
+                self.preview_list = Vec::new();
+                self.progress_state = ProgressState::Creating;
+            },
             Message::WordSearchFieldString((index, val)) => {
                 self.word_search_list[index].base_word = val;
+                self.word_search_list[index].edited();
             },
             Message::WordSearchFieldPickList((index, val)) => {
                 for type_name in &*ALL_GEN_TYPES {
                     if val == *type_name {
-                        self.word_search_list[index].search_type = &type_name;
+                        self.word_search_list[index].search_type = type_name;
                     }
                 }
+                self.word_search_list[index].edited();
+            },
+            Message::WordSearchFieldHidden((index, val)) => {
+                self.word_search_list[index].message = val;
+            },
+            Message::WordSearchFieldTitle((index, val)) => {
+                self.word_search_list[index].title = val;
+            },
+            Message::WordSearchFieldSubtitle((index, val)) => {
+                self.word_search_list[index].subtitle = val;
+            },
+            Message::WordSearchFieldLetters((index, val)) => {
+                self.word_search_list[index].letter_count = val;
+            },
+            Message::WordSearchFieldWords((index, val)) => {
+                self.word_search_list[index].word_count = val;
+            },
+            Message::WordSearchFieldDirections((index, val)) => {
+                self.word_search_list[index].directions = val;
+            },
+            Message::WordSearchFieldOptions(index) => {
+                self.word_search_list[index].show_options = !self.word_search_list[index].show_options;
             },
             Message::Letter(val) => {
                 self.letter_count = val;
@@ -266,7 +845,89 @@ impl Application for Gui {
             Message::PageFormat(val) => {
                 for format in &*ALL_FORMATS {
                     if val == *format {
-                        self.page_format = &format;
+                        self.page_format = format;
+                    }
+                }
+            },
+            Message::CustomWidth(val) => {
+                self.custom_width = val;
+            },
+            Message::CustomHeight(val) => {
+                self.custom_height = val;
+            },
+            Message::SizeUnit(val) => {
+                for unit in &*ALL_UNITS {
+                    if val == *unit {
+                        self.size_unit = unit;
+                    }
+                }
+            },
+            Message::Orientation(val) => {
+                for orientation in &*ALL_ORIENTATIONS {
+                    if val == *orientation {
+                        self.orientation = orientation;
+                    }
+                }
+            },
+            Message::Margins(val) => {
+                self.margins = val;
+            },
+            Message::ClassName(val) => {
+                self.class_name = val;
+            },
+            Message::PageLayout(val) => {
+                for layout in &*ALL_LAYOUTS {
+                    if val == *layout {
+                        self.page_layout = layout;
+                    }
+                }
+            },
+            Message::PuzzleKind(val) => {
+                for kind in &*ALL_KINDS {
+                    if val == *kind {
+                        self.kind = kind;
+                    }
+                }
+            },
+            Message::WordOrder(val) => {
+                for order in &*ALL_WORD_ORDERS {
+                    if val == *order {
+                        self.word_order = order;
+                    }
+                }
+            },
+            Message::Language(val) => {
+                for language in &*ALL_LANGUAGES {
+                    if val == *language {
+                        self.language = language;
+                    }
+                }
+            },
+            Message::Seed(val) => {
+                self.seed = val;
+            },
+            Message::Checkboxes(val) => {
+                self.checkboxes = val;
+            },
+            Message::Clues(val) => {
+                self.clues = val;
+            },
+            Message::Pictures(val) => {
+                self.pictures = val;
+            },
+            Message::CheckWords(val) => {
+                self.check_words = val;
+            },
+            Message::ClueFile(val) => {
+                self.clue_file = val;
+            },
+            Message::AnswerKey(val) => {
+                self.answer_key = val;
+            },
+            Message::OutputFormat(val) => {
+                for output in &*ALL_OUTPUTS {
+                    if val == *output {
+                        self.output_format = output;
                     }
                 }
             },
@@ -282,29 +943,222 @@ impl Application for Gui {
             Message::SettingsPageFormat(val) => {
                 for format in &*ALL_FORMATS {
                     if val == *format {
-                        self.page_format_sett = &format;
+                        self.page_format_sett = format;
                     }
                 }
             },
             Message::SettingsSaveDir(val) => {
                 self.save_dir_sett = val;
             },
+            Message::SettingsHeader(val) => {
+                self.header_sett = val;
+            },
+            Message::SettingsFooter(val) => {
+                self.footer_sett = val;
+            },
+            Message::SettingsClassName(val) => {
+                self.class_name_sett = val;
+            },
+            Message::SettingsTitleFont(val) => {
+                self.title_font_sett = val;
+            },
+            Message::SettingsGridFont(val) => {
+                self.grid_font_sett = val;
+            },
+            Message::SettingsListFont(val) => {
+                self.list_font_sett = val;
+            },
+            Message::SettingsDirections(val) => {
+                self.directions_sett = val;
+            },
             Message::SettingsTheme(val) => {
-                match val.as_str() {
-                    "Light" => self.theme_sett = &ALL_THEMES[0],
-                    "Dark" => self.theme_sett = &ALL_THEMES[1],
-                    _ => self.theme_sett = &ALL_THEMES[0]
+                self.theme_sett = val;
+                self.apply_theme(&self.theme_sett.clone(), self.high_contrast_sett);
+            },
+            Message::SettingsLargeText(val) => {
+                self.large_text_sett = val;
+                self.large_text = val;
+            },
+            Message::SettingsHighContrast(val) => {
+                self.high_contrast_sett = val;
+                self.apply_theme(&self.theme_sett.clone(), val);
+            },
+            Message::SettingsVerboseLog(val) => {
+                self.verbose_log_sett = val;
+            },
+            Message::OpenLogFolder => {
+                let opened = match logging::get_log_directory() {
+                    Some(val) => dialog::open_path(&val.to_string_lossy()),
+                    None => false
+                };
+                if !opened {
+                    self.err_msg = String::from("Error: The Log Folder Could Not Be Opened");
+                    self.err = true;
                 }
             },
+            Message::KeyPressed((key_code, modifiers)) => {
+                return self.handle_key(key_code, modifiers);
+            },
+            Message::CheckSystemTheme => {
+                styling::update_system_dark();
+            },
             Message::Refresh => {
+                if self.theme_name == styling::SYSTEM_THEME && !self.high_contrast { //Follow the desktop
+                    if styling::system_prefers_dark() != self.system_dark {
+                        self.apply_theme(&self.theme_name.clone(), false);
+                    }
+                }
+                if let ProgressState::Creating = self.progress_state {
+                    self.check_word_counts();
+                }
                 match get_gen_status() {
                     GenStatus::InProgress => (),
+                    GenStatus::Generated => {
+                        set_gen_status(GenStatus::InProgress);
+                        self.preview_list = take_preview().into_iter().map(|word_search| PreviewItem::new(word_search, false)).collect();
+                        if self.preview_list.is_empty() { //Nothing to preview, only the error messages are left
+                            self.show_finished();
+                        }
+                        else {
+                            self.progress_state = ProgressState::Previewing;
+                        }
+                    },
                     GenStatus::Done => {
                         set_gen_status(GenStatus::InProgress);
-                        self.progress_state = ProgressState::Finished;
+                        self.show_finished();
+                    }
+                }
+            },
+            Message::FileName(val) => {
+                self.file_name = val;
+            },
+            Message::PerPuzzle(val) => {
+                self.per_puzzle = val;
+            },
+            Message::IfExists(val) => {
+                for mode in &*ALL_CONFLICT_MODES {
+                    if val == *mode {
+                        self.if_exists = mode;
+                    }
+                }
+            },
+            Message::ProjectPath(val) => {
+                self.project_path = val;
+            },
+            Message::BrowseProject => {
+                let dialog = dialog::pick_file("Open Project", self.project_path.clone(), &["*.json"], "Project Files");
+                return Command::perform(dialog, |path| Message::PathPicked((PathTarget::Project, path)));
+            },
+            Message::BrowseSaveDir => {
+                let dialog = dialog::pick_folder("Save to", self.save_dir.clone());
+                return Command::perform(dialog, |path| Message::PathPicked((PathTarget::SaveDir, path)));
+            },
+            Message::BrowseSettingsSaveDir => {
+                let dialog = dialog::pick_folder("Default Save Directory", self.save_dir_sett.clone());
+                return Command::perform(dialog, |path| Message::PathPicked((PathTarget::SettingsSaveDir, path)));
+            },
+            Message::BrowseClueFile => {
+                let dialog = dialog::pick_file("Clue File", self.clue_file.clone(), &["*.txt"], "Text Files");
+                return Command::perform(dialog, |path| Message::PathPicked((PathTarget::ClueFile, path)));
+            },
+            Message::BrowseWordList(index) => {
+                let dialog = dialog::pick_file("Custom Word List", self.word_search_list[index].base_word.clone(), &["*.txt"], "Text Files");
+                return Command::perform(dialog, move |path| Message::PathPicked((PathTarget::WordList(index), path)));
+            },
+            Message::PathPicked((target, path)) => {
+                let path = match path {
+                    Some(val) => val,
+                    None => return Command::none() //Cancelled
+                };
+
+                match target {
+                    PathTarget::Project => self.project_path = path,
+                    PathTarget::SaveDir => self.save_dir = path,
+                    PathTarget::SettingsSaveDir => self.save_dir_sett = path,
+                    PathTarget::ClueFile => self.clue_file = path,
+                    PathTarget::WordList(index) => {
+                        if let Some(item) = self.word_search_list.get_mut(index) { //The row may have been removed while the dialog was open
+                            item.base_word = path;
+                            item.edited();
+                        }
+                    },
+                    PathTarget::SaveProject => {
+                        self.project_path = path;
+                        return self.update(Message::SaveProject);
+                    }
+                }
+            },
+            Message::SaveProject => {
+                if self.project_path.trim() == "" {
+                    let dialog = dialog::pick_save_file("Save Project", String::from("project.json"), &["*.json"], "Project Files");
+                    return Command::perform(dialog, |path| Message::PathPicked((PathTarget::SaveProject, path)));
+                }
+
+                let mut entries: Vec<ProjectEntry> = Vec::new();
+                for item in &self.word_search_list {
+                    if let WordSearchFieldType::Input = item.field_type {
+                        entries.push(ProjectEntry {
+                            base_word: item.base_word.clone(),
+                            search_type: get_search_type(item.search_type),
+                            title: item.title.clone(),
+                            subtitle: item.subtitle.clone(),
+                            message: item.message.clone(),
+                            letter_count: item.letter_count.trim().parse::<u8>().ok(),
+                            word_count: item.word_count.trim().parse::<u8>().ok(),
+                            directions: item.directions
+                        });
+                    }
+                }
+
+                if !project::save_project(self.project_path.trim(), &Project { word_searches: entries }) {
+                    self.err_msg = format!("Error: The Project Could Not Be Saved to \"{}\"", self.project_path.trim());
+                    self.err = true;
+                    return Command::none();
+                }
+                self.err = false;
+            },
+            Message::LoadProject => {
+                let project = match project::load_project(self.project_path.trim()) {
+                    Some(val) => val,
+                    None => {
+                        self.err_msg = format!("Error: \"{}\" is Not a Valid Project File", self.project_path.trim());
+                        self.err = true;
+                        return Command::none();
+                    }
+                };
+
+                self.word_search_list = Vec::new();
+                for (index, entry) in project.word_searches.into_iter().enumerate() {
+                    let mut field = WordSearchField::new(index);
+                    field.field_type = WordSearchFieldType::Input;
+                    field.base_word = entry.base_word;
+                    field.search_type = get_search_type_name(entry.search_type);
+                    field.title = entry.title;
+                    field.subtitle = entry.subtitle;
+                    field.message = entry.message;
+                    field.letter_count = entry.letter_count.map(|val| val.to_string()).unwrap_or_default();
+                    field.word_count = entry.word_count.map(|val| val.to_string()).unwrap_or_default();
+                    field.directions = entry.directions;
+                    self.word_search_list.push(field);
+                }
+                let len = self.word_search_list.len();
+                self.word_search_list.push(WordSearchField::new(len));
+                self.err = false;
+            },
+            Message::OpenFile(index) => {
+                if let Some(path) = self.finished_list[index].result.path.clone() {
+                    if !dialog::open_path(&path) {
+                        self.err_msg = format!("Error: \"{}\" Could Not Be Opened", path);
+                        self.err = true;
                     }
                 }
             },
+            Message::OpenFolder => {
+                if !dialog::open_path(&self.finished_dir) {
+                    self.err_msg = format!("Error: \"{}\" Could Not Be Opened", self.finished_dir);
+                    self.err = true;
+                }
+            },
             Message::Reset => {
                 self.progress_state = ProgressState::Creating;
                 self.reset();
@@ -314,52 +1168,90 @@ impl Application for Gui {
             },
             Message::SaveSettings => {
                 let theme = self.theme_sett.clone();
-                let word_count = match self.word_count_sett.parse::<f64>() { //Floats so all numbers are numbers
-                    Ok(val) => {
-                        if val >= 10.0 && val <= 20.0 {
-                            val as u8
-                        }
-                        else {
-                            self.err = true;
-                            self.err_msg = format!("Error: {} is outside the range of word counts", self.word_count_sett);
-                            return Command::none();
-                        }
-                    },
-                    Err(_) => {
+                let word_count = match parse_count(&self.word_count_sett, WORD_COUNT_RANGE, "Word Count") {
+                    Ok(val) => val,
+                    Err(err) => {
                         self.err = true;
-                        self.err_msg = format!("Error: {} is not a number", self.word_count_sett);
+                        self.err_msg = err;
                         return Command::none();
                     }
                 };
-                let letter_count = match self.letter_count_sett.parse::<f64>() {
-                    Ok(val) => {
-                        if val >= 8.0 && val <= 14.0 {
-                            val as u8
-                        }
-                        else {
-                            self.err = true;
-                            self.err_msg = format!("Error: {} is outside the range of letter counts", self.word_count_sett);
-                            return Command::none();
-                        }
-                    },
-                    Err(_) => {
+                let letter_count = match parse_count(&self.letter_count_sett, LETTER_COUNT_RANGE, "Letter Count") {
+                    Ok(val) => val,
+                    Err(err) => {
                         self.err = true;
-                        self.err_msg = format!("Error: {} is not a number", self.letter_count_sett);
+                        self.err_msg = err;
                         return Command::none();
                     }
                 };
                 let format = self.page_format_sett.clone();
                 let save_dir = self.save_dir_sett.clone();
+                if save_dir.trim() != "" { //No default directory is allowed
+                    if let Err(err) = output::check_directory(&save_dir) {
+                        self.err = true;
+                        self.err_msg = format!("Error: {}", err);
+                        return Command::none();
+                    }
+                }
+                //The word search settings that are shown become the defaults, so they have to be valid
+                let seed = match parse_seed(&self.seed) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        self.err = true;
+                        self.err_msg = err;
+                        return Command::none();
+                    }
+                };
+                if let Err(err) = self.get_page_setup() {
+                    self.err = true;
+                    self.err_msg = err;
+                    return Command::none();
+                }
+                if !self.directions_sett.horizontal && !self.directions_sett.vertical && !self.directions_sett.diagonal {
+                    self.err = true;
+                    self.err_msg = String::from("Error: At Least One Direction Is Needed");
+                    return Command::none();
+                }
 
                 let prefs = Preferences {
-                    theme: theme,
-                    word_count: word_count,
-                    letter_count: letter_count,
-                    format: format,
-                    save_directory: save_dir
+                    theme,
+                    word_count,
+                    letter_count,
+                    format,
+                    save_directory: save_dir,
+                    header_template: self.header_sett.clone(),
+                    footer_template: self.footer_sett.clone(),
+                    class_name: self.class_name_sett.clone(),
+                    title_font: self.title_font_sett.clone(),
+                    grid_font: self.grid_font_sett.clone(),
+                    list_font: self.list_font_sett.clone(),
+                    large_text: self.large_text_sett,
+                    high_contrast: self.high_contrast_sett,
+                    verbose_log: self.verbose_log_sett,
+                    kind: get_puzzle_kind(self.kind),
+                    directions: self.directions_sett,
+                    language: get_language(self.language),
+                    seed,
+                    custom_width: self.custom_width.trim().to_string(),
+                    custom_height: self.custom_height.trim().to_string(),
+                    size_unit: self.size_unit.clone(),
+                    landscape: self.orientation == "Landscape",
+                    margins: self.margins.trim().to_string(),
+                    layout: get_layout(self.page_layout),
+                    word_order: get_word_order(self.word_order),
+                    checkboxes: self.checkboxes,
+                    clues: self.clues,
+                    pictures: self.pictures,
+                    answer_key: self.answer_key,
+                    check_words: self.check_words,
+                    output_format: self.output_format.clone(),
+                    file_name: self.file_name.clone(),
+                    per_puzzle: self.per_puzzle,
+                    if_exists: self.if_exists.clone(),
+                    ..Preferences::default()
                 };
 
-                if config::save_preferences(prefs.clone()) == false {
+                if !config::save_preferences(prefs.clone()) {
                     self.err = true;
                     self.err_msg = String::from("Error: Failed to save settings");
                     return Command::none()
@@ -371,7 +1263,22 @@ impl Application for Gui {
                 self.word_search_list = word_searches;
                 self.progress_state = ProgressState::Creating;
             },
-            Message::ReturnFromSettings => {
+            Message::ReturnFromSettings => { //Changes that weren't saved are undone
+                let prefs = match config::load_preferences(true) {
+                    Ok(val) => val,
+                    Err(err) => {
+                        self.err_msg = format!("Error: {}", err);
+                        self.err = true;
+                        config::get_defaults()
+                    }
+                };
+                self.apply_theme(&prefs.theme, prefs.high_contrast);
+                self.theme_sett = prefs.theme;
+                self.high_contrast_sett = prefs.high_contrast;
+                self.large_text = prefs.large_text;
+                self.large_text_sett = prefs.large_text;
+                self.directions_sett = self.directions;
+                self.verbose_log_sett = prefs.verbose_log;
                 self.progress_state = ProgressState::Creating;
             }
         }
@@ -380,14 +1287,35 @@ impl Application for Gui {
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        time::every(std::time::Duration::from_millis(250))
-            .map(|_| Message::Refresh)
+        let mut subscriptions = vec![
+            time::every(std::time::Duration::from_millis(250))
+                .map(|_| Message::Refresh),
+            iced_native::subscription::events_with(get_key_message)
+        ];
+        if self.theme_name == styling::SYSTEM_THEME && !self.high_contrast { //The desktop is only asked while its theme is used
+            subscriptions.push(
+                time::every(std::time::Duration::from_secs(2))
+                    .map(|_| Message::CheckSystemTheme)
+            );
+        }
+
+        Subscription::batch(subscriptions)
+    }
+
+    fn scale_factor(&self) -> f64 {
+        if self.large_text {
+            1.25
+        }
+        else {
+            1.0
+        }
     }
 
-    fn view(&mut self) -> Element<Message> {
+    fn view(&mut self) -> Element<'_, Message> {
         match self.progress_state {
             ProgressState::Creating => self.draw_creating(),
             ProgressState::Generating => self.draw_generating(),
+            ProgressState::Previewing => self.draw_previewing(),
             ProgressState::Finished => self.draw_finished(),
             ProgressState::ChangingSettings => self.draw_settings()
         }
@@ -395,7 +1323,7 @@ impl Application for Gui {
 }
 
 impl Gui {
-    fn draw_creating(&mut self) -> Element<Message> {
+    fn draw_creating(&mut self) -> Element<'_, Message> {
         //General
         let item_width = 200;
 
@@ -409,15 +1337,40 @@ impl Gui {
 
         word_search_scroll = word_search_scroll.push(Text::new("Word Searches to Generate:").size(TITLE_2_SIZE)); //Title
 
-        for item in &mut self.word_search_list {
-            word_search_scroll = word_search_scroll.push(Rule::horizontal(50)); //Bar to divide elements
+        let project_row = Row::with_children( //Saving and loading the list of word searches
+            vec![
+                Text::new("Project File:").into(),
+                TextInput::new(&mut self.project_path_in, "Path to a .json File", &self.project_path, Message::ProjectPath)
+                .width(Length::Units(item_width))
+                .style(self.theme.clone())
+                .into(),
+                Button::new(&mut self.browse_project_button, Text::new("Browse"))
+                .on_press(Message::BrowseProject)
+                .style(self.theme.clone())
+                .into(),
+                Button::new(&mut self.save_project_button, Text::new("Save"))
+                .on_press(Message::SaveProject)
+                .style(self.theme.clone())
+                .into(),
+                Button::new(&mut self.load_project_button, Text::new("Load"))
+                .on_press(Message::LoadProject)
+                .style(self.theme.clone())
+                .into()
+            ]
+        ).spacing(15).align_items(Align::Center);
+        word_search_scroll = word_search_scroll.push(project_row);
+
+        let row_count = self.word_search_list.len();
+        for item in &mut self.word_search_list {
+            word_search_scroll = word_search_scroll.push(Rule::horizontal(50)); //Bar to divide elements
             match item.field_type {
                 WordSearchFieldType::Input => { //Elements that accept input
                     let item_index = item.index;
-                    let word_field = Row::with_children( //Element to accept the base word for generation
+                    let placeholder = if get_search_type(item.search_type) == SearchType::Custom { &self.word_list_hint } else { "Type in Base Word Here" };
+                    let mut word_field = Row::with_children( //Element to accept the base word for generation
                         vec![
                             Text::new("Base Word:").into(),
-                            TextInput::new(&mut item.base_word_in, "Type in Base Word Here", &item.base_word, move |val| {
+                            TextInput::new(&mut item.base_word_in, placeholder, &item.base_word, move |val| {
                                 Message::WordSearchFieldString((item_index, val))
                             })
                             .width(Length::Units(item_width))
@@ -425,6 +1378,13 @@ impl Gui {
                             .into()
                         ]
                     ).spacing(15);
+                    if get_search_type(item.search_type) == SearchType::Custom { //The base word is a file path
+                        word_field = word_field.push(
+                            Button::new(&mut item.browse_button, Text::new("Browse"))
+                            .on_press(Message::BrowseWordList(item_index))
+                            .style(self.theme.clone())
+                        );
+                    }
 
                     let item_index = item.index;
                     let type_field = Row::with_children( //Element to accept the type of word search for generation
@@ -444,8 +1404,135 @@ impl Gui {
                         ]
                     ).spacing(15);
 
-                    word_search_scroll = word_search_scroll.push(word_field)
-                    .push(type_field)
+                    let item_index = item.index;
+                    let message_field = Row::with_children( //Element to accept an optional hidden message
+                        vec![
+                            Text::new("Hidden Message:").into(),
+                            TextInput::new(&mut item.message_in, "Optional", &item.message, move |val| {
+                                Message::WordSearchFieldHidden((item_index, val))
+                            })
+                            .width(Length::Units(item_width))
+                            .style(self.theme.clone())
+                            .into()
+                        ]
+                    ).spacing(15);
+
+                    word_search_scroll = word_search_scroll.push(word_field);
+                    if !item.err_msg.is_empty() {
+                        word_search_scroll = word_search_scroll.push(Text::new(&item.err_msg).size(18).color(ERROR_COLOR));
+                    }
+                    else if !item.warning.is_empty() {
+                        word_search_scroll = word_search_scroll.push(Text::new(&item.warning).size(18).color(ERROR_COLOR));
+                    }
+                    word_search_scroll = word_search_scroll.push(type_field)
+                    .push(message_field);
+
+                    let options_text = if item.show_options { "Hide Options" } else { "More Options" };
+                    let mut up_button = Button::new(&mut item.up_button, Text::new("Move Up"))
+                    .style(self.theme.clone());
+                    if item_index > 0 {
+                        up_button = up_button.on_press(Message::MoveWordSearchUp(item_index));
+                    }
+                    let mut down_button = Button::new(&mut item.down_button, Text::new("Move Down"))
+                    .style(self.theme.clone());
+                    if item_index + 2 < row_count {
+                        down_button = down_button.on_press(Message::MoveWordSearchDown(item_index));
+                    }
+                    word_search_scroll = word_search_scroll.push(
+                        Row::with_children( //Buttons to change the list of word searches
+                            vec![
+                                Button::new(&mut item.options_button, Text::new(options_text))
+                                .on_press(Message::WordSearchFieldOptions(item_index))
+                                .style(self.theme.clone())
+                                .into(),
+                                up_button.into(),
+                                down_button.into(),
+                                Button::new(&mut item.duplicate_button, Text::new("Duplicate"))
+                                .on_press(Message::DuplicateWordSearch(item_index))
+                                .style(self.theme.clone())
+                                .into(),
+                                Button::new(&mut item.remove_button, Text::new("Remove"))
+                                .on_press(Message::RemoveWordSearch(item_index))
+                                .style(self.theme.clone())
+                                .into()
+                            ]
+                        ).spacing(10)
+                    );
+                    if item.show_options { //Settings for only this word search
+                        let title_field = Row::with_children(
+                            vec![
+                                Text::new("Title:").into(),
+                                TextInput::new(&mut item.title_in, "From Base Word", &item.title, move |val| {
+                                    Message::WordSearchFieldTitle((item_index, val))
+                                })
+                                .width(Length::Units(item_width))
+                                .style(self.theme.clone())
+                                .into()
+                            ]
+                        ).spacing(15);
+
+                        let subtitle_field = Row::with_children(
+                            vec![
+                                Text::new("Instructions:").into(),
+                                TextInput::new(&mut item.subtitle_in, "Optional", &item.subtitle, move |val| {
+                                    Message::WordSearchFieldSubtitle((item_index, val))
+                                })
+                                .width(Length::Units(item_width))
+                                .style(self.theme.clone())
+                                .into()
+                            ]
+                        ).spacing(15);
+
+                        let count_field = Row::with_children(
+                            vec![
+                                Text::new("Letters per Row:").into(),
+                                TextInput::new(&mut item.letter_count_in, &self.letter_count.to_string(), &item.letter_count, move |val| {
+                                    Message::WordSearchFieldLetters((item_index, val))
+                                })
+                                .width(Length::Units(50))
+                                .style(self.theme.clone())
+                                .into(),
+                                Text::new("Words:").into(),
+                                TextInput::new(&mut item.word_count_in, &self.word_count.to_string(), &item.word_count, move |val| {
+                                    Message::WordSearchFieldWords((item_index, val))
+                                })
+                                .width(Length::Units(50))
+                                .style(self.theme.clone())
+                                .into()
+                            ]
+                        ).spacing(15).align_items(Align::Center);
+
+                        let directions = item.directions;
+                        let direction_field = Row::with_children(
+                            vec![
+                                Checkbox::new(directions.horizontal, "Across", move |val| {
+                                    Message::WordSearchFieldDirections((item_index, Directions { horizontal: val, ..directions }))
+                                })
+                                .style(self.theme.clone())
+                                .into(),
+                                Checkbox::new(directions.vertical, "Down", move |val| {
+                                    Message::WordSearchFieldDirections((item_index, Directions { vertical: val, ..directions }))
+                                })
+                                .style(self.theme.clone())
+                                .into(),
+                                Checkbox::new(directions.diagonal, "Diagonal", move |val| {
+                                    Message::WordSearchFieldDirections((item_index, Directions { diagonal: val, ..directions }))
+                                })
+                                .style(self.theme.clone())
+                                .into(),
+                                Checkbox::new(directions.backwards, "Backwards", move |val| {
+                                    Message::WordSearchFieldDirections((item_index, Directions { backwards: val, ..directions }))
+                                })
+                                .style(self.theme.clone())
+                                .into()
+                            ]
+                        ).spacing(15);
+
+                        word_search_scroll = word_search_scroll.push(title_field)
+                        .push(subtitle_field)
+                        .push(count_field)
+                        .push(direction_field);
+                    }
                 },
                 WordSearchFieldType::New => { //Element for creating new input elements
                     word_search_scroll = word_search_scroll.push(
@@ -458,33 +1545,34 @@ impl Gui {
         }
 
         //Create Word Search Settings Element
-        let settings_spacing = 50;
+        let settings_spacing = 30;
         let settings_mini_spacing = 5;
 
-        let mut settings_col = Column::new()
+        let mut settings_col = Scrollable::new(&mut self.settings_scroll)
         .width(Length::Fill)
         .height(Length::Fill)
+        .scrollbar_width(10)
         .align_items(Align::Center)
         .push(Text::new("Word Search Settings:").size(TITLE_2_SIZE))
         .push(Space::with_height(Length::Units(settings_spacing)));
 
-        settings_col = settings_col.push(Text::new("Number of Letters per Row (8 to 14):")) //Letter count slider
+        settings_col = settings_col.push(Text::new(format!("Number of Letters per Row ({}):", get_range_text(LETTER_COUNT_RANGE)))) //Letter count slider
         .push(Space::with_height(Length::Units(settings_mini_spacing)))
         .push(
             Slider::new(
                 &mut self.letter_count_sl,
-                (8 as u8)..=14,
+                LETTER_COUNT_RANGE,
                 self.letter_count,
                 Message::Letter
             ).width(Length::Units(item_width))
         ).push(Space::with_height(Length::Units(settings_spacing)));
 
-        settings_col = settings_col.push(Text::new("Maximum Number of Words in List (10 to 20):")) //Word count slider
+        settings_col = settings_col.push(Text::new(format!("Maximum Number of Words in List ({}):", get_range_text(WORD_COUNT_RANGE)))) //Word count slider
         .push(Space::with_height(Length::Units(settings_mini_spacing)))
         .push(
             Slider::new(
                 &mut self.word_count_sl,
-                (10 as u8)..=20,
+                WORD_COUNT_RANGE,
                 self.word_count,
                 Message::WordNum
             ).width(Length::Units(item_width))
@@ -500,20 +1588,258 @@ impl Gui {
             Message::PageFormat
             )
             .style(self.theme.clone())
+        );
+
+        if self.page_format == "Custom" { //Custom page size
+            let size_row = Row::with_children(
+                vec![
+                    TextInput::new(&mut self.custom_width_in, "Width", &self.custom_width, Message::CustomWidth)
+                    .width(Length::Units(70))
+                    .style(self.theme.clone())
+                    .into(),
+                    Text::new("x").into(),
+                    TextInput::new(&mut self.custom_height_in, "Height", &self.custom_height, Message::CustomHeight)
+                    .width(Length::Units(70))
+                    .style(self.theme.clone())
+                    .into(),
+                    PickList::new(
+                        &mut self.size_unit_pl,
+                        Cow::from(&*ALL_UNITS),
+                        Some(self.size_unit.to_string()),
+                        Message::SizeUnit
+                    )
+                    .style(self.theme.clone())
+                    .into()
+                ]
+            ).spacing(10).align_items(Align::Center);
+
+            settings_col = settings_col.push(Space::with_height(Length::Units(settings_mini_spacing)))
+            .push(size_row);
+        }
+        settings_col = settings_col.push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Orientation:")) //Page orientation list
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+            &mut self.orientation_pl,
+            Cow::from(&*ALL_ORIENTATIONS),
+            Some(self.orientation.to_string()),
+            Message::Orientation
+            )
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Margins in mm (all or top, right, bottom, left):")) //Margins
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            TextInput::new(
+                &mut self.margins_in,
+                "Enter the Margins",
+                &self.margins,
+                Message::Margins
+            )
+            .width(Length::Units(item_width))
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Class Name:")) //Class name for the header and footer
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            TextInput::new(
+                &mut self.class_name_in,
+                "Enter the Class Name",
+                &self.class_name,
+                Message::ClassName
+            )
+            .width(Length::Units(item_width))
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Pages per Sheet:")) //Page layout list
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+            &mut self.page_layout_pl,
+            Cow::from(&*ALL_LAYOUTS),
+            Some(self.page_layout.to_string()),
+            Message::PageLayout
+            )
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Puzzle Type:")) //Puzzle kind list
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+            &mut self.kind_pl,
+            Cow::from(&*ALL_KINDS),
+            Some(self.kind.to_string()),
+            Message::PuzzleKind
+            )
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Language of the Words Found Online:")) //Datamuse vocabulary
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+            &mut self.language_pl,
+            Cow::from(&*ALL_LANGUAGES),
+            Some(self.language.to_string()),
+            Message::Language
+            )
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Seed (the same seed gives the same puzzles):")) //Repeatable puzzles
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            TextInput::new(
+                &mut self.seed_in,
+                "Random",
+                &self.seed,
+                Message::Seed
+            )
+            .width(Length::Units(item_width))
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Word List Order:")) //Word list options
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+            &mut self.word_order_pl,
+            Cow::from(&*ALL_WORD_ORDERS),
+            Some(self.word_order.to_string()),
+            Message::WordOrder
+            )
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(
+            Checkbox::new(self.checkboxes, "Checkboxes Next to the Words", Message::Checkboxes)
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(
+            Checkbox::new(self.clues, "Print Clues Instead of Words", Message::Clues)
+            .style(self.theme.clone())
+        );
+
+        if self.clues {
+            settings_col = settings_col.push(Space::with_height(Length::Units(settings_mini_spacing)))
+            .push(Text::new("Clue File (one \"word: clue\" per line, optional):"))
+            .push(Space::with_height(Length::Units(settings_mini_spacing)))
+            .push(
+                Row::new()
+                .push(
+                    TextInput::new(
+                        &mut self.clue_file_in,
+                        "Use Definitions",
+                        &self.clue_file,
+                        Message::ClueFile
+                    )
+                    .width(Length::Units(item_width))
+                    .style(self.theme.clone())
+                )
+                .push(
+                    Button::new(&mut self.browse_clue_file_button, Text::new("Browse"))
+                    .on_press(Message::BrowseClueFile)
+                    .style(self.theme.clone())
+                )
+                .spacing(10)
+                .align_items(Align::Center)
+            );
+        }
+        settings_col = settings_col.push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(
+            Checkbox::new(self.pictures, "Show Pictures of Custom Lists", Message::Pictures)
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(
+            Checkbox::new(self.answer_key, "Add Answer Key Pages", Message::AnswerKey)
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(
+            Checkbox::new(self.check_words, "Check Word Counts Online", Message::CheckWords)
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("Output Format:")) //Output format list
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+            &mut self.output_format_pl,
+            Cow::from(&*ALL_OUTPUTS),
+            Some(self.output_format.to_string()),
+            Message::OutputFormat
+            )
+            .style(self.theme.clone())
         )
         .push(Space::with_height(Length::Units(settings_spacing)));
 
         settings_col = settings_col.push(Text::new("Save to:")) //Save Directory
         .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            Row::new()
+            .push(
+                TextInput::new(
+                    &mut self.save_dir_in,
+                    "Enter a Directory",
+                    &self.save_dir,
+                    Message::SaveDir
+                )
+                .width(Length::Units(item_width))
+                .style(self.theme.clone())
+            )
+            .push(
+                Button::new(&mut self.browse_save_dir_button, Text::new("Browse"))
+                .on_press(Message::BrowseSaveDir)
+                .style(self.theme.clone())
+            )
+            .spacing(10)
+            .align_items(Align::Center)
+        ).push(Space::with_height(Length::Units(settings_spacing)));
+
+        settings_col = settings_col.push(Text::new("File Name ({date}, {time}, {title}, {n}):")) //Output file naming
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
         .push(
             TextInput::new(
-                &mut self.save_dir_in,
-                "Enter a Directory",
-                &self.save_dir,
-                Message::SaveDir
+                &mut self.file_name_in,
+                "wordsearch",
+                &self.file_name,
+                Message::FileName
             )
             .width(Length::Units(item_width))
             .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(
+            Checkbox::new(self.per_puzzle, "One File per Puzzle", Message::PerPuzzle)
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(Text::new("If the File Exists:"))
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+            &mut self.if_exists_pl,
+            Cow::from(&*ALL_CONFLICT_MODES),
+            Some(self.if_exists.to_string()),
+            Message::IfExists
+            )
+            .style(self.theme.clone())
         ).push(Space::with_height(Length::Units(60)));
 
         settings_col = settings_col.push( //Generate Button
@@ -562,7 +1888,7 @@ impl Gui {
         .into()
     }
 
-    fn draw_generating(&mut self) -> Element<Message> {
+    fn draw_generating(&mut self) -> Element<'_, Message> {
         let mut col = Column::new()
         .height(Length::Fill)
         .width(Length::Fill)
@@ -576,7 +1902,7 @@ impl Gui {
         }
         else {
             progress = pdf::get_progress();
-            text = Text::new("Creating Pdf...").size(75);
+            text = Text::new(format!("Creating {}...", get_output_name(self.output_format))).size(75);
         }
 
         col = col.push(Space::with_height(Length::Units(200)))
@@ -593,13 +1919,169 @@ impl Gui {
         .into()
     }
 
-    fn draw_finished(&mut self) -> Element<Message> {
+    fn draw_previewing(&mut self) -> Element<'_, Message> {
+        let theme = self.theme.clone();
+        let accepted = self.preview_list.iter().filter(|item| item.accepted).count();
+        let mut preview_scroll = Scrollable::new(&mut self.preview_scroll)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .scrollbar_width(10)
+        .align_items(Align::Center)
+        .spacing(10);
+
+        preview_scroll = preview_scroll.push(Text::new("Preview:").size(TITLE_SIZE));
+        for result in get_results() { //Word searches that couldn't be generated
+            preview_scroll = preview_scroll.push(Text::new(format!("{}: {}", result.title, result.err_msg.unwrap_or_default())).size(15));
+        }
+
+        for (index, item) in self.preview_list.iter_mut().enumerate() {
+            preview_scroll = preview_scroll.push(Rule::horizontal(50)); //Bar to divide elements
+
+            let mut word_col = Column::new()
+            .spacing(5)
+            .push(Text::new(&item.word_search.title).size(TITLE_2_SIZE));
+
+            let alternatives = &item.alternatives;
+            let removable = item.words.len() > 1;
+            for word in item.words.iter_mut() {
+                let mut remove_button = Button::new(&mut word.remove_button, Text::new("Remove"))
+                .style(theme.clone());
+                if removable {
+                    remove_button = remove_button.on_press(Message::PreviewRemoveWord((index, word.word.clone())));
+                }
+
+                let old_word = word.word.clone();
+                word_col = word_col.push(
+                    Row::with_children(
+                        vec![
+                            Text::new(&word.word).width(Length::Units(150)).into(),
+                            remove_button.into(),
+                            Space::with_width(Length::Units(10)).into(),
+                            Text::new("Swap with:").into(),
+                            Space::with_width(Length::Units(5)).into(),
+                            PickList::new(
+                                &mut word.swap_pl,
+                                Cow::from(alternatives),
+                                None,
+                                move |val| Message::PreviewSwapWord((index, (old_word.clone(), val)))
+                            )
+                            .style(theme.clone())
+                            .into()
+                        ]
+                    )
+                    .align_items(Align::Center)
+                );
+            }
+
+            word_col = word_col.push(Space::with_height(Length::Units(10)))
+            .push(
+                Row::with_children(
+                    vec![
+                        Button::new(&mut item.regenerate_button, Text::new("Regenerate"))
+                        .on_press(Message::PreviewRegenerate(index))
+                        .style(theme.clone())
+                        .into(),
+                        Space::with_width(Length::Units(20)).into(),
+                        Checkbox::new(item.accepted, "Accept for Export", move |val| Message::PreviewAccept((index, val)))
+                        .style(theme.clone())
+                        .into()
+                    ]
+                )
+                .align_items(Align::Center)
+            );
+            if !item.err_msg.is_empty() {
+                word_col = word_col.push(Text::new(&item.err_msg).color(ERROR_COLOR));
+            }
+
+            preview_scroll = preview_scroll.push(
+                Row::with_children(
+                    vec![
+                        Canvas::new(GridPreview::new(&item.word_search.word_search))
+                        .width(Length::Units(320))
+                        .height(Length::Units(320))
+                        .into(),
+                        Space::with_width(Length::Units(30)).into(),
+                        word_col.into()
+                    ]
+                )
+            );
+        }
+
+        let mut export_button = Button::new(&mut self.export_button, Text::new(format!("Export {} Accepted", accepted)).size(20))
+        .style(theme.clone());
+        if accepted > 0 {
+            export_button = export_button.on_press(Message::Export);
+        }
+
+        let button_row = Row::with_children(
+            vec![
+                Button::new(&mut self.preview_back_button, Text::new("Back").size(20))
+                .on_press(Message::ReturnFromPreview)
+                .style(theme.clone())
+                .into(),
+                Space::with_width(Length::Fill).into(),
+                Button::new(&mut self.accept_all_button, Text::new("Accept All").size(20))
+                .on_press(Message::PreviewAcceptAll)
+                .style(theme.clone())
+                .into(),
+                Space::with_width(Length::Units(20)).into(),
+                export_button.into()
+            ]
+        )
+        .padding(10);
+
+        let mut col = Column::new()
+        .push(preview_scroll)
+        .push(Rule::horizontal(10));
+        if !self.conflicts.is_empty() { //Asks before replacing files
+            col = col.push(Text::new(format!("Already exists: {}", self.conflicts.join(", "))).size(20))
+            .push(
+                Row::with_children(
+                    vec![
+                        Button::new(&mut self.overwrite_button, Text::new("Overwrite"))
+                        .on_press(Message::ConflictOverwrite)
+                        .style(theme.clone())
+                        .into(),
+                        Button::new(&mut self.rename_button, Text::new("Add a Number"))
+                        .on_press(Message::ConflictRename)
+                        .style(theme.clone())
+                        .into(),
+                        Button::new(&mut self.cancel_button, Text::new("Cancel"))
+                        .on_press(Message::ConflictCancel)
+                        .style(theme.clone())
+                        .into()
+                    ]
+                )
+                .spacing(15)
+                .padding(10)
+            );
+        }
+        if self.err {
+            col = col.push(Text::new(&self.err_msg));
+        }
+        col = col.push(button_row);
+
+        Container::new(col)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme)
+        .into()
+    }
+
+    fn draw_finished(&mut self) -> Element<'_, Message> {
         let mut col = Column::new()
         .height(Length::Fill)
         .width(Length::Fill)
         .align_items(Align::Center);
 
-        col = col.push(Space::with_height(Length::Units(200)))
+        let written = self.finished_list.iter().any(|item| item.result.path.is_some());
+        let mut open_folder_button = Button::new(&mut self.open_folder_button, Text::new("Show Folder").size(30))
+        .style(self.theme.clone());
+        if written {
+            open_folder_button = open_folder_button.on_press(Message::OpenFolder);
+        }
+
+        col = col.push(Space::with_height(Length::Units(100)))
         .push(
             Text::new("Finished Generating Word Searches!")
             .horizontal_alignment(HorizontalAlignment::Center)
@@ -607,21 +2089,55 @@ impl Gui {
         )
         .push(Space::with_height(Length::Units(50)))
         .push(
-            Button::new(&mut self.finished_button, Text::new("Click Here to Continue").size(50))
-            .on_press(Message::Reset)
-            .style(self.theme.clone())
+            Row::with_children(
+                vec![
+                    open_folder_button.into(),
+                    Button::new(&mut self.generate_again_button, Text::new("Generate Again").size(30)) //Same word searches and settings
+                    .on_press(Message::StartGenerate)
+                    .style(self.theme.clone())
+                    .into(),
+                    Button::new(&mut self.finished_button, Text::new("Continue").size(30))
+                    .on_press(Message::Reset)
+                    .style(self.theme.clone())
+                    .into()
+                ]
+            ).spacing(20)
         );
 
-        let err_msgs = get_err_msgs();
-        if err_msgs.len() > 0 {
-            col = col.push(Space::with_height(Length::Units(50)))
-            .push(Text::new("Error messages:").size(30));
+        if self.err {
+            col = col.push(Space::with_height(Length::Units(10)))
+            .push(Text::new(&self.err_msg));
+        }
 
-            for msg in err_msgs {
-                col = col.push(Space::with_height(Length::Units(10)))
-                .push(Text::new(&msg).size(15));
+        let mut results_scroll = Scrollable::new(&mut self.finished_scroll)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .scrollbar_width(10)
+        .align_items(Align::Center)
+        .spacing(10);
+        for (index, item) in self.finished_list.iter_mut().enumerate() { //One line for every word search
+            let mut row = Row::new()
+            .spacing(15)
+            .align_items(Align::Center)
+            .push(Text::new(&item.result.title).size(20));
+            match (&item.result.path, &item.result.err_msg) {
+                (Some(path), _) => {
+                    row = row.push(Text::new(format!("Saved to {}", path)).size(15))
+                    .push(
+                        Button::new(&mut item.open_button, Text::new("Open"))
+                        .on_press(Message::OpenFile(index))
+                        .style(self.theme.clone())
+                    );
+                },
+                (None, Some(err)) => {
+                    row = row.push(Text::new(format!("Failed: {}", err)).size(15).color(ERROR_COLOR));
+                },
+                (None, None) => ()
             }
+            results_scroll = results_scroll.push(row);
         }
+        col = col.push(Space::with_height(Length::Units(50)))
+        .push(results_scroll);
 
         Container::new(col)
         .width(Length::Fill)
@@ -632,7 +2148,7 @@ impl Gui {
         .into()
     }
 
-    fn draw_settings(&mut self) -> Element<Message> {
+    fn draw_settings(&mut self) -> Element<'_, Message> {
         let settings_spacing = 35;
         let settings_mini_spacing = 15;
         let item_width = Length::Units(310);
@@ -649,9 +2165,10 @@ impl Gui {
             ]
         );
 
-        let mut col = Column::new()
+        let mut col = Scrollable::new(&mut self.settings_page_scroll)
         .width(Length::Fill)
         .height(Length::Fill)
+        .scrollbar_width(10)
         .align_items(Align::Center)
         .push(return_button_row)
         .push(Text::new("Settings:").size(TITLE_SIZE))
@@ -662,33 +2179,45 @@ impl Gui {
         .push(
             PickList::new(
                 &mut self.theme_sett_pl,
-                Cow::from(&*ALL_THEMES),
-                Some(self.theme_sett.to_string()),
+                Cow::from(&self.theme_names),
+                Some(self.theme_sett.clone()),
                 Message::SettingsTheme
             )
             .style(self.theme.clone())
         ).push(Space::with_height(Length::Units(settings_spacing)));
+
+        col = col.push(Text::new("Accessibility:"))
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            Checkbox::new(self.large_text_sett, "Large Text", Message::SettingsLargeText)
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing * 2)))
+        .push(
+            Checkbox::new(self.high_contrast_sett, "High Contrast", Message::SettingsHighContrast)
+            .style(self.theme.clone())
+        ).push(Space::with_height(Length::Units(settings_spacing)));
         
-        col = col.push(Text::new("Number of Letters per Row (8 to 14):")) //Letter count field
+        col = col.push(Text::new(format!("Number of Letters per Row ({}):", get_range_text(LETTER_COUNT_RANGE)))) //Letter count field
         .push(Space::with_height(Length::Units(settings_mini_spacing)))
         .push(
             TextInput::new(
                 &mut self.letter_count_sett_in,
                 "Enter Default Letter Count Here",
-                &mut self.letter_count_sett,
+                &self.letter_count_sett,
                 Message::SettingsLetter
             )
             .width(item_width)
             .style(self.theme.clone())
         ).push(Space::with_height(Length::Units(settings_spacing)));
 
-        col = col.push(Text::new("Maximum Number of Words in List (10 to 20):")) //Word count field
+        col = col.push(Text::new(format!("Maximum Number of Words in List ({}):", get_range_text(WORD_COUNT_RANGE)))) //Word count field
         .push(Space::with_height(Length::Units(settings_mini_spacing)))
         .push(
             TextInput::new(
                 &mut self.word_count_sett_in,
                 "Enter Default Word Count Here",
-                &mut self.word_count_sett,
+                &self.word_count_sett,
                 Message::SettingsWordNum
             )
             .width(item_width)
@@ -710,15 +2239,163 @@ impl Gui {
 
         col = col.push(Text::new("Save to:")) //Save Directory
         .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            Row::new()
+            .push(
+                TextInput::new(
+                    &mut self.save_dir_sett_in,
+                    "Enter the Default Save Directory Here",
+                    &self.save_dir_sett,
+                    Message::SettingsSaveDir
+                )
+                .width(item_width)
+                .style(self.theme.clone())
+            )
+            .push(
+                Button::new(&mut self.browse_save_dir_sett_button, Text::new("Browse"))
+                .on_press(Message::BrowseSettingsSaveDir)
+                .style(self.theme.clone())
+            )
+            .spacing(10)
+            .align_items(Align::Center)
+        ).push(Space::with_height(Length::Units(settings_spacing)));
+
+        col = col.push(Text::new("Title Font:")) //Fonts
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+                &mut self.title_font_sett_pl,
+                Cow::from(&self.font_names),
+                Some(self.title_font_sett.clone()),
+                Message::SettingsTitleFont
+            )
+            .style(self.theme.clone())
+        ).push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(Text::new("Grid Font:"))
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+                &mut self.grid_font_sett_pl,
+                Cow::from(&self.font_names),
+                Some(self.grid_font_sett.clone()),
+                Message::SettingsGridFont
+            )
+            .style(self.theme.clone())
+        ).push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(Text::new("Word List Font:"))
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            PickList::new(
+                &mut self.list_font_sett_pl,
+                Cow::from(&self.font_names),
+                Some(self.list_font_sett.clone()),
+                Message::SettingsListFont
+            )
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(Text::new("TrueType and OpenType fonts can be added to the fonts folder of the data directory").size(15))
+        .push(Space::with_height(Length::Units(settings_spacing)));
+
+        col = col.push(Text::new("Page Header:")) //Header template
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
         .push(
             TextInput::new(
-                &mut self.save_dir_sett_in,
-                "Enter the Default Save Directory Here",
-                &self.save_dir_sett,
-                Message::SettingsSaveDir
+                &mut self.header_sett_in,
+                "Enter the Header Template Here",
+                &self.header_sett,
+                Message::SettingsHeader
+            )
+            .width(Length::Units(500))
+            .style(self.theme.clone())
+        ).push(Space::with_height(Length::Units(settings_spacing)));
+
+        col = col.push(Text::new("Page Footer:")) //Footer template
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            TextInput::new(
+                &mut self.footer_sett_in,
+                "Enter the Footer Template Here",
+                &self.footer_sett,
+                Message::SettingsFooter
+            )
+            .width(Length::Units(500))
+            .style(self.theme.clone())
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            Text::new("Use | to separate the left, center and right part. Placeholders: {title}, {class}, {date}, {page}, {pages}")
+            .size(15)
+        ).push(Space::with_height(Length::Units(settings_spacing)));
+
+        col = col.push(Text::new("Default Class Name:")) //Class name
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            TextInput::new(
+                &mut self.class_name_sett_in,
+                "Enter the Default Class Name Here",
+                &self.class_name_sett,
+                Message::SettingsClassName
             )
             .width(item_width)
             .style(self.theme.clone())
+        ).push(Space::with_height(Length::Units(settings_spacing)));
+
+        let directions = self.directions_sett;
+        col = col.push(Text::new("Directions of New Word Searches:")) //Default directions
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            Row::with_children(
+                vec![
+                    Checkbox::new(directions.horizontal, "Across", move |val| {
+                        Message::SettingsDirections(Directions { horizontal: val, ..directions })
+                    })
+                    .style(self.theme.clone())
+                    .into(),
+                    Checkbox::new(directions.vertical, "Down", move |val| {
+                        Message::SettingsDirections(Directions { vertical: val, ..directions })
+                    })
+                    .style(self.theme.clone())
+                    .into(),
+                    Checkbox::new(directions.diagonal, "Diagonal", move |val| {
+                        Message::SettingsDirections(Directions { diagonal: val, ..directions })
+                    })
+                    .style(self.theme.clone())
+                    .into(),
+                    Checkbox::new(directions.backwards, "Backwards", move |val| {
+                        Message::SettingsDirections(Directions { backwards: val, ..directions })
+                    })
+                    .style(self.theme.clone())
+                    .into()
+                ]
+            ).spacing(15)
+        )
+        .push(Space::with_height(Length::Units(settings_spacing)))
+        .push(
+            Text::new("Saving also keeps the word search settings of the main page (puzzle type, page, word list, language, seed and output) as the defaults")
+            .size(15)
+        ).push(Space::with_height(Length::Units(settings_spacing)));
+
+        col = col.push(Text::new("Log File:")) //Diagnosing problems
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            Row::new()
+            .push(
+                Checkbox::new(self.verbose_log_sett, "Verbose Log", Message::SettingsVerboseLog)
+                .style(self.theme.clone())
+            )
+            .push(
+                Button::new(&mut self.open_log_button, Text::new("Show Log Folder"))
+                .on_press(Message::OpenLogFolder)
+                .style(self.theme.clone())
+            )
+            .spacing(20)
+            .align_items(Align::Center)
+        )
+        .push(Space::with_height(Length::Units(settings_mini_spacing)))
+        .push(
+            Text::new("Verbose logs list the queries, every generation attempt and the words that couldn't be placed")
+            .size(15)
         ).push(Space::with_height(Length::Units(60)));
 
         col = col.push( //Save Button
@@ -744,19 +2421,341 @@ impl Gui {
         .into()
     }
 
+    fn get_page_setup(&self) -> Result<PageSetup, String> {
+        let (width, height) = if self.page_format == "Custom" {
+            let width = self.custom_width.trim().parse::<f32>();
+            let height = self.custom_height.trim().parse::<f32>();
+            match (width, height) {
+                (Ok(width), Ok(height)) if width > 0.0 && height > 0.0 => {
+                    if self.size_unit == "in" {
+                        (pdf::in_to_points(width), pdf::in_to_points(height))
+                    }
+                    else {
+                        (pdf::mm_to_points(width), pdf::mm_to_points(height))
+                    }
+                },
+                _ => return Err(String::from("Error: The Custom Page Size Is Not Valid"))
+            }
+        }
+        else {
+            pdf::get_format(self.page_format)
+        };
+
+        let margins = match Margins::parse(&self.margins) {
+            Some(val) => val,
+            None => return Err(format!("Error: \"{}\" Are Not Valid Margins", self.margins))
+        };
+
+        let page = PageSetup::new(width, height, self.orientation == "Landscape", margins);
+        if !margins.fit(page.width, page.height) {
+            return Err(String::from("Error: The Margins Are Too Large for the Page"));
+        }
+
+        Ok(page)
+    }
+
+    fn export(&mut self, file_options: FileOptions) {
+        let page = match self.get_page_setup() {
+            Ok(val) => val,
+            Err(err) => {
+                self.err_msg = err;
+                self.err = true;
+                return;
+            }
+        };
+
+        self.progress_state = ProgressState::Generating;
+        self.finished_dir = file_options.directory.clone();
+
+        //Spawn export thread with the accepted word searches
+        let results: Vec<TitledWordSearch> = self.preview_list.drain(..)
+            .filter(|item| item.accepted)
+            .map(|item| item.word_search)
+            .collect();
+        let layout = get_layout(self.page_layout);
+        let header = self.header_template.clone();
+        let footer = self.footer_template.clone();
+        let class_name = self.class_name.clone();
+        let fonts = (self.title_font.clone(), self.grid_font.clone(), self.list_font.clone());
+        let word_list_options = WordListOptions {
+            order: get_word_order(self.word_order),
+            checkboxes: self.checkboxes,
+            clues: self.clues,
+            pictures: self.pictures
+        };
+        let answer_key = self.answer_key;
+        let output = self.output_format.clone();
+        let extension = get_extension(self.output_format);
+        thread::spawn(move || {
+            let titles: Vec<String> = results.iter().map(|word_search| word_search.title.clone()).collect();
+            let files = match output::get_files(results, &file_options, extension) {
+                Ok(val) => val,
+                Err(err) => { //Nothing could be written
+                    log::error!("\"{}\" could not be created: {}", file_options.directory, err);
+                    for title in titles {
+                        add_result(title, None, Some(format!("{}: {}", file_options.directory, err)));
+                    }
+                    set_gen_status(GenStatus::Done);
+                    return;
+                }
+            };
+
+            let text_options = TextOptions {
+                coordinates: true,
+                answers: answer_key,
+                markdown: output == "Markdown"
+            };
+            let pdf_options = PdfOptions {
+                page,
+                layout,
+                header,
+                footer,
+                class_name,
+                title_font: fonts::load_font(&fonts.0),
+                grid_font: fonts::load_font(&fonts.1),
+                list_font: fonts::load_font(&fonts.2),
+                word_list: word_list_options,
+                answer_key
+            };
+            for (path, word_search_list) in files {
+                let titles: Vec<String> = word_search_list.iter().map(|word_search| word_search.title.clone()).collect();
+                let result = match output.as_str() {
+                    "HTML" => html::create_html(word_search_list, &path),
+                    "Text" | "Markdown" => text::create_text(word_search_list, &path, &text_options),
+                    _ => pdf::create_pdf(word_search_list, &pdf_options, &path)
+                };
+                for title in titles {
+                    match &result {
+                        Ok(_) => add_result(title, Some(path.clone()), None),
+                        Err(err) => add_result(title, None, Some(format!("{}: {}", path, err)))
+                    }
+                }
+            }
+
+            set_gen_status(GenStatus::Done);
+        });
+    }
+
+    //Asks Datamuse how many words it finds for rows that haven't changed for a second
+    fn check_word_counts(&mut self) {
+        for (index, key, warning) in take_word_checks() {
+            for item in self.word_search_list.iter_mut() {
+                if item.index == index && item.get_check_key() == key {
+                    item.warning = warning.clone();
+                }
+            }
+        }
+        if !self.check_words {
+            return;
+        }
+
+        for item in self.word_search_list.iter_mut() {
+            if let WordSearchFieldType::New = item.field_type {
+                continue;
+            }
+            let stype = get_search_type(item.search_type);
+            let key = item.get_check_key();
+            if stype == SearchType::Custom || item.checked == key || item.last_edit.elapsed() < Duration::from_secs(1) {
+                continue;
+            }
+            item.checked = key.clone();
+            if validate_base_word(&item.base_word, stype).is_err() {
+                continue;
+            }
+
+            let index = item.index;
+            let word = item.base_word.replace(" ", "");
+            let language = get_language(self.language);
+            thread::spawn(move || {
+                let warning = match word_list::count_words(&word, stype, language) {
+                    Ok(count) if count < 8 => format!("Only {} Words Were Found Online, at Least 8 Are Needed", count),
+                    _ => String::new() //Connection errors are shown when generating
+                };
+                add_word_check((index, key, warning));
+            });
+        }
+    }
+
+    //Tab and Shift+Tab move between the inputs, Enter adds a row or generates,
+    //Ctrl+S saves and Escape goes back
+    fn handle_key(&mut self, key_code: KeyCode, modifiers: Modifiers) -> Command<Message> {
+        match (&self.progress_state, key_code) {
+            (ProgressState::Creating, KeyCode::Tab) | (ProgressState::ChangingSettings, KeyCode::Tab) => {
+                self.focus_next(modifiers.shift);
+            },
+            (ProgressState::Creating, KeyCode::Enter) if !modifiers.is_command_pressed() => {
+                if self.project_path_in.is_focused() {
+                    return self.update(Message::LoadProject);
+                }
+                if !self.word_search_list.iter().any(|item| item.is_focused()) {
+                    return self.update(Message::StartGenerate);
+                }
+
+                for item in self.word_search_list.iter_mut() {
+                    item.base_word_in.unfocus();
+                    item.message_in.unfocus();
+                    item.title_in.unfocus();
+                    item.subtitle_in.unfocus();
+                    item.letter_count_in.unfocus();
+                    item.word_count_in.unfocus();
+                }
+                let command = self.update(Message::AddWordSearch);
+                let len = self.word_search_list.len();
+                self.word_search_list[len - 2].base_word_in.focus();
+                return command;
+            },
+            (ProgressState::Creating, KeyCode::Enter) => {
+                return self.update(Message::StartGenerate);
+            },
+            (ProgressState::Creating, KeyCode::S) if modifiers.is_command_pressed() => {
+                return self.update(Message::SaveProject);
+            },
+            (ProgressState::ChangingSettings, KeyCode::S) if modifiers.is_command_pressed() => {
+                return self.update(Message::SaveSettings);
+            },
+            (ProgressState::ChangingSettings, KeyCode::Escape) => {
+                return self.update(Message::ReturnFromSettings);
+            },
+            (ProgressState::Previewing, KeyCode::Escape) if !self.conflicts.is_empty() => {
+                return self.update(Message::ConflictCancel);
+            },
+            _ => ()
+        }
+
+        Command::none()
+    }
+
+    fn focus_next(&mut self, backwards: bool) {
+        let mut inputs = self.get_inputs();
+        let count = inputs.len();
+        if count == 0 {
+            return;
+        }
+
+        let next = match inputs.iter().position(|input| input.is_focused()) {
+            Some(index) => {
+                inputs[index].unfocus();
+                if backwards { (index + count - 1) % count } else { (index + 1) % count }
+            },
+            None => if backwards { count - 1 } else { 0 }
+        };
+        inputs[next].focus();
+        inputs[next].move_cursor_to_end();
+    }
+
+    //Text inputs of the current screen in the order they are shown
+    fn get_inputs(&mut self) -> Vec<&mut text_input::State> {
+        let mut inputs: Vec<&mut text_input::State> = Vec::new();
+        match self.progress_state {
+            ProgressState::Creating => {
+                inputs.push(&mut self.project_path_in);
+                for item in self.word_search_list.iter_mut() {
+                    if let WordSearchFieldType::New = item.field_type {
+                        continue;
+                    }
+                    inputs.push(&mut item.base_word_in);
+                    inputs.push(&mut item.message_in);
+                    if item.show_options {
+                        inputs.push(&mut item.title_in);
+                        inputs.push(&mut item.subtitle_in);
+                        inputs.push(&mut item.letter_count_in);
+                        inputs.push(&mut item.word_count_in);
+                    }
+                }
+
+                if self.page_format == "Custom" {
+                    inputs.push(&mut self.custom_width_in);
+                    inputs.push(&mut self.custom_height_in);
+                }
+                inputs.push(&mut self.margins_in);
+                inputs.push(&mut self.class_name_in);
+                if self.clues {
+                    inputs.push(&mut self.clue_file_in);
+                }
+                inputs.push(&mut self.save_dir_in);
+                inputs.push(&mut self.file_name_in);
+            },
+            ProgressState::ChangingSettings => {
+                inputs.push(&mut self.letter_count_sett_in);
+                inputs.push(&mut self.word_count_sett_in);
+                inputs.push(&mut self.save_dir_sett_in);
+                inputs.push(&mut self.header_sett_in);
+                inputs.push(&mut self.footer_sett_in);
+                inputs.push(&mut self.class_name_sett_in);
+            },
+            _ => ()
+        }
+
+        inputs
+    }
+
+    //High contrast replaces the chosen theme
+    fn apply_theme(&mut self, name: &str, high_contrast: bool) {
+        self.theme = if high_contrast {
+            styling::load_theme("High Contrast")
+        }
+        else {
+            styling::load_theme(name)
+        };
+        self.theme_name = name.to_string();
+        self.high_contrast = high_contrast;
+        if name == styling::SYSTEM_THEME {
+            self.system_dark = styling::system_prefers_dark();
+        }
+    }
+
+    //Messages of the rows use the index, so it has to match the position
+    fn reindex(&mut self) {
+        for (index, item) in self.word_search_list.iter_mut().enumerate() {
+            item.index = index;
+        }
+    }
+
+    fn show_finished(&mut self) {
+        self.finished_list = get_results().into_iter().map(|result| FinishedItem {
+            result,
+            open_button: button::State::new()
+        }).collect();
+        self.err = false;
+        self.progress_state = ProgressState::Finished;
+    }
+
+    fn get_file_options(&self) -> FileOptions {
+        FileOptions {
+            directory: self.save_dir.clone(),
+            template: self.file_name.clone(),
+            per_puzzle: self.per_puzzle,
+            overwrite: self.if_exists == "Overwrite",
+            time: self.export_time
+        }
+    }
+
+    //Words that can't be placed are left out by the generator, so a swapped in word is checked afterwards
+    fn regenerate_preview(&mut self, index: usize, word_list: Vec<String>, new_word: Option<String>) {
+        let item = &mut self.preview_list[index];
+        let word_search = match request::regenerate(&item.word_search, &word_list) {
+            Ok(val) => val,
+            Err(err) => {
+                item.err_msg = format!("Error: {}", err);
+                return;
+            }
+        };
+
+        let mut results = vec![word_search];
+        request::add_clues(&mut results, &self.preview_clues);
+        *item = PreviewItem::new(results.remove(0), item.accepted);
+        if let Some(word) = new_word {
+            if !item.word_search.word_search.word_list.contains(&word) {
+                item.err_msg = format!("Error: {} Could Not Be Placed", word);
+            }
+        }
+    }
+
     fn reset(&mut self) {
         self.word_search_list = vec![
-            WordSearchField {
-                base_word_in: text_input::State::new(),
-                base_word: String::from(""),
-                search_type_pl: pick_list::State::default(),
-                search_type: &ALL_GEN_TYPES[0],
-                new_button: button::State::new(),
-                field_type: WordSearchFieldType::New,
-                index: 0
-            }
+            WordSearchField::new(0)
         ];
-        set_err_msgs(Vec::new());
+        set_results(Vec::new());
         self.err = false;
         self.err_msg = String::new();
     }
@@ -764,30 +2763,79 @@ impl Gui {
     fn new_from_prefs(prefs: Preferences) -> Self {
         let mut gui = Gui::default();
         
-        if prefs.theme == &*ALL_THEMES[0] {
-            gui.theme = Theme::Light;
-        }
-        else if prefs.theme == &*ALL_THEMES[1] {
-            gui.theme = Theme::Dark;
-        }
+        gui.apply_theme(&prefs.theme, prefs.high_contrast);
+        gui.theme_sett = prefs.theme.clone();
+        gui.large_text = prefs.large_text;
+        gui.large_text_sett = prefs.large_text;
+        gui.high_contrast_sett = prefs.high_contrast;
+        gui.verbose_log_sett = prefs.verbose_log;
+        logging::set_verbose(prefs.verbose_log);
 
         for format in &*ALL_FORMATS {
             if prefs.format == *format {
-                gui.page_format = &format;
-                gui.page_format_sett = &format;
+                gui.page_format = format;
+                gui.page_format_sett = format;
             }
         }
 
-        if prefs.word_count >= 10 && prefs.word_count <= 20 {
+        if WORD_COUNT_RANGE.contains(&prefs.word_count) {
             gui.word_count = prefs.word_count;
             gui.word_count_sett = prefs.word_count.to_string();
         }
-        if prefs.letter_count >= 8 && prefs.letter_count <= 14 {
+        if LETTER_COUNT_RANGE.contains(&prefs.letter_count) {
             gui.letter_count = prefs.letter_count;
             gui.letter_count_sett = prefs.letter_count.to_string();
         }
         gui.save_dir = prefs.save_directory.clone();
         gui.save_dir_sett = prefs.save_directory;
+        gui.header_template = prefs.header_template.clone();
+        gui.header_sett = prefs.header_template;
+        gui.footer_template = prefs.footer_template.clone();
+        gui.footer_sett = prefs.footer_template;
+        gui.class_name = prefs.class_name.clone();
+        gui.class_name_sett = prefs.class_name;
+        gui.title_font = prefs.title_font.clone();
+        gui.title_font_sett = prefs.title_font;
+        gui.grid_font = prefs.grid_font.clone();
+        gui.grid_font_sett = prefs.grid_font;
+        gui.list_font = prefs.list_font.clone();
+        gui.list_font_sett = prefs.list_font;
+
+        //Word search settings
+        gui.kind = get_name(&ALL_KINDS, get_puzzle_kind, prefs.kind);
+        gui.language = get_name(&ALL_LANGUAGES, get_language, prefs.language);
+        gui.page_layout = get_name(&ALL_LAYOUTS, get_layout, prefs.layout);
+        gui.word_order = get_name(&ALL_WORD_ORDERS, get_word_order, prefs.word_order);
+        gui.seed = prefs.seed.map(|seed| seed.to_string()).unwrap_or_default();
+        gui.directions = prefs.directions;
+        gui.directions_sett = prefs.directions;
+        gui.word_search_list[0].directions = prefs.directions;
+        gui.custom_width = prefs.custom_width;
+        gui.custom_height = prefs.custom_height;
+        for unit in &*ALL_UNITS {
+            if prefs.size_unit == *unit {
+                gui.size_unit = unit;
+            }
+        }
+        gui.orientation = if prefs.landscape { &ALL_ORIENTATIONS[1] } else { &ALL_ORIENTATIONS[0] };
+        gui.margins = prefs.margins;
+        gui.checkboxes = prefs.checkboxes;
+        gui.clues = prefs.clues;
+        gui.pictures = prefs.pictures;
+        gui.answer_key = prefs.answer_key;
+        gui.check_words = prefs.check_words;
+        for output in &*ALL_OUTPUTS {
+            if prefs.output_format == *output {
+                gui.output_format = output;
+            }
+        }
+        gui.file_name = prefs.file_name;
+        gui.per_puzzle = prefs.per_puzzle;
+        for mode in &*ALL_CONFLICT_MODES {
+            if prefs.if_exists == *mode {
+                gui.if_exists = mode;
+            }
+        }
 
         gui
     }
@@ -800,35 +2848,103 @@ impl Gui {
             word_count: 15,
             page_format_pl: pick_list::State::default(),
             page_format: &ALL_FORMATS[0],
+            custom_width_in: text_input::State::new(),
+            custom_width: String::from("210"),
+            custom_height_in: text_input::State::new(),
+            custom_height: String::from("297"),
+            size_unit_pl: pick_list::State::default(),
+            size_unit: &ALL_UNITS[0],
+            orientation_pl: pick_list::State::default(),
+            orientation: &ALL_ORIENTATIONS[0],
+            margins_in: text_input::State::new(),
+            margins: String::from("15"),
+            class_name_in: text_input::State::new(),
+            class_name: String::from(""),
+            header_template: String::from(""),
+            footer_template: String::from(""),
+            title_font: String::from("Times Roman"),
+            grid_font: String::from("Times Roman"),
+            list_font: String::from("Times Roman"),
+            page_layout_pl: pick_list::State::default(),
+            page_layout: &ALL_LAYOUTS[0],
+            kind_pl: pick_list::State::default(),
+            kind: &ALL_KINDS[0],
+            word_order_pl: pick_list::State::default(),
+            word_order: &ALL_WORD_ORDERS[0],
+            language_pl: pick_list::State::default(),
+            language: &ALL_LANGUAGES[0],
+            seed_in: text_input::State::new(),
+            seed: String::from(""),
+            directions: Directions::all(),
+            checkboxes: false,
+            clues: false,
+            pictures: false,
+            check_words: false,
+            clue_file_in: text_input::State::new(),
+            clue_file: String::from(""),
+            answer_key: false,
+            output_format_pl: pick_list::State::default(),
+            output_format: &ALL_OUTPUTS[0],
             save_dir_in: text_input::State::new(),
             save_dir: String::from(""),
+            file_name_in: text_input::State::new(),
+            file_name: String::from("wordsearch"),
+            per_puzzle: false,
+            if_exists_pl: pick_list::State::default(),
+            if_exists: &ALL_CONFLICT_MODES[0],
+            project_path_in: text_input::State::new(),
+            project_path: String::from(""),
+            save_project_button: button::State::new(),
+            load_project_button: button::State::new(),
+            browse_project_button: button::State::new(),
+            browse_save_dir_button: button::State::new(),
+            browse_clue_file_button: button::State::new(),
             go_to_settings_button: button::State::new(),
 
             gen_button: button::State::new(),
+            settings_scroll: scrollable::State::new(),
             word_search_list: vec![
-                WordSearchField {
-                    base_word_in: text_input::State::new(),
-                    base_word: String::from(""),
-                    search_type_pl: pick_list::State::default(),
-                    search_type: &ALL_GEN_TYPES[0],
-                    new_button: button::State::new(),
-                    field_type: WordSearchFieldType::New,
-                    index: 0
-                }
+                WordSearchField::new(0)
             ],
+            word_list_hint: format!("File or {}", assets::get_word_list_names().join(", ")),
             word_search_list_scroll: scrollable::State::new(),
 
             progress_state: ProgressState::Creating,
             finished_button: button::State::new(),
+            finished_list: Vec::new(),
+            finished_dir: String::from(""),
+            finished_scroll: scrollable::State::new(),
+            open_folder_button: button::State::new(),
+            generate_again_button: button::State::new(),
+            preview_list: Vec::new(),
+            preview_clues: HashMap::new(),
+            preview_scroll: scrollable::State::new(),
+            preview_back_button: button::State::new(),
+            accept_all_button: button::State::new(),
+            export_button: button::State::new(),
+            conflicts: Vec::new(),
+            export_time: Local::now(),
+            overwrite_button: button::State::new(),
+            rename_button: button::State::new(),
+            cancel_button: button::State::new(),
 
             err_msg: String::from(""),
             err: false,
 
-            theme: Theme::Light,
+            theme: styling::load_theme("Light"),
 
             theme_sett_pl: pick_list::State::default(),
-            theme_sett: &ALL_THEMES[0],
-        	word_count_sett_in: text_input::State::new(),
+            theme_sett: String::from("Light"),
+            theme_names: styling::get_theme_names(),
+            theme_name: String::from("Light"),
+            high_contrast: false,
+            system_dark: false,
+            large_text: false,
+            large_text_sett: false,
+            high_contrast_sett: false,
+            verbose_log_sett: false,
+            open_log_button: button::State::new(),
+            word_count_sett_in: text_input::State::new(),
             word_count_sett: String::from(""),
             letter_count_sett_in: text_input::State::new(),
             letter_count_sett: String::from(""),
@@ -836,6 +2952,22 @@ impl Gui {
             page_format_sett: &ALL_FORMATS[0],
             save_dir_sett_in: text_input::State::new(),
             save_dir_sett: String::from(""),
+            browse_save_dir_sett_button: button::State::new(),
+            header_sett_in: text_input::State::new(),
+            header_sett: String::from(""),
+            footer_sett_in: text_input::State::new(),
+            footer_sett: String::from(""),
+            class_name_sett_in: text_input::State::new(),
+            class_name_sett: String::from(""),
+            font_names: fonts::get_font_names(),
+            title_font_sett_pl: pick_list::State::default(),
+            title_font_sett: String::from("Times Roman"),
+            grid_font_sett_pl: pick_list::State::default(),
+            grid_font_sett: String::from("Times Roman"),
+            list_font_sett_pl: pick_list::State::default(),
+            list_font_sett: String::from("Times Roman"),
+            directions_sett: Directions::all(),
+            settings_page_scroll: scrollable::State::new(),
             save_sett_button: button::State::new(),
             return_button: button::State::new()
         }
@@ -849,22 +2981,151 @@ fn get_search_type(stype: &str) -> SearchType {
         "Ends with [Letters]" => SearchType::EndsWith,
         "Sounds like [Word]" => SearchType::SoundsLike,
         "Comes before [Word]" => SearchType::BlankWord,
+        "Custom List [File]" => SearchType::Custom,
         _ => SearchType::WordBlank //Follows [Word]
     }
 }
 
-fn get_format(format: &str) -> (f32, f32) {
-    match format {
-        "Letter" => (612.0, 792.0),
-        "Half Letter" => (396.0, 612.0),
-        "DINA4" => (595.0, 842.0),
-        _ => (420.0, 595.0) //DINA5
+fn get_search_type_name(stype: SearchType) -> &'static String {
+    match stype {
+        SearchType::RelatedTo => &ALL_GEN_TYPES[0],
+        SearchType::RhymesWith => &ALL_GEN_TYPES[1],
+        SearchType::EndsWith => &ALL_GEN_TYPES[2],
+        SearchType::SoundsLike => &ALL_GEN_TYPES[3],
+        SearchType::BlankWord => &ALL_GEN_TYPES[4],
+        SearchType::WordBlank => &ALL_GEN_TYPES[5],
+        SearchType::Custom => &ALL_GEN_TYPES[6]
+    }
+}
+
+fn get_layout(layout: &str) -> Layout {
+    match layout {
+        "Two per Page" => Layout::TwoUp,
+        "Four per Page" => Layout::FourUp,
+        "Booklet" => Layout::Booklet,
+        _ => Layout::Single //One per Page
+    }
+}
+
+fn get_word_order(order: &str) -> WordOrder {
+    match order {
+        "Alphabetical" => WordOrder::Alphabetical,
+        "By Length" => WordOrder::Length,
+        _ => WordOrder::Insertion
+    }
+}
+
+fn get_puzzle_kind(kind: &str) -> PuzzleKind {
+    match kind {
+        "Fill-In" => PuzzleKind::FillIn,
+        "Criss-Cross" => PuzzleKind::CrissCross,
+        _ => PuzzleKind::WordSearch
+    }
+}
+
+fn get_language(language: &str) -> Language {
+    match language {
+        "Spanish" => Language::Spanish,
+        _ => Language::English
+    }
+}
+
+//Finds the pick list entry of a saved setting, unknown values use the first entry
+fn get_name<T: PartialEq>(names: &'static [String], get_value: fn(&str) -> T, value: T) -> &'static String {
+    match names.iter().find(|name| get_value(name) == value) {
+        Some(val) => val,
+        None => &names[0]
+    }
+}
+
+//Base words are sent to Datamuse, custom lists have to be files
+fn validate_base_word(word: &str, stype: SearchType) -> Result<(), String> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(String::from("The Base Word Is Missing"));
+    }
+
+    match stype {
+        SearchType::Custom => {
+            if !Path::new(word).is_file() && assets::load_word_list(word).is_none() {
+                return Err(format!("The Word List \"{}\" Does Not Exist", word));
+            }
+        },
+        SearchType::EndsWith => {
+            if word.contains(char::is_whitespace) {
+                return Err(String::from("\"Ends With\" Needs the Last Letters of a Word (e.g. \"ing\"), Not Words"));
+            }
+            if let Some(c) = word.chars().find(|c| !c.is_alphabetic()) {
+                return Err(format!("\"{}\" Is Not a Letter", c));
+            }
+        },
+        _ => {
+            if let Some(c) = word.chars().find(|c| !c.is_alphabetic() && !c.is_whitespace() && *c != '\'' && *c != '-') {
+                return Err(format!("\"{}\" Is Not Supported in Base Words", c));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+//Empty fields fall back to the general setting, anything else has to be a number in range
+fn parse_override(value: &str, default: u8, range: RangeInclusive<u8>) -> Option<u8> {
+    if value.trim() == "" {
+        return Some(default);
+    }
+
+    match value.trim().parse::<u8>() {
+        Ok(val) if range.contains(&val) => Some(val),
+        _ => None
+    }
+}
+
+//Settings are only checked if they are numbers, so fractions are cut off
+fn parse_count(value: &str, range: RangeInclusive<u8>, name: &str) -> Result<u8, String> {
+    match value.trim().parse::<f64>() {
+        Ok(val) if val >= *range.start() as f64 && val <= *range.end() as f64 => Ok(val as u8),
+        Ok(_) => Err(format!("Error: The {} Must Be From {}", name, get_range_text(range))),
+        Err(_) => Err(format!("Error: {} is not a number", value))
+    }
+}
+
+//Empty means a different puzzle every time
+fn parse_seed(seed: &str) -> Result<Option<u64>, String> {
+    if seed.trim() == "" {
+        return Ok(None);
+    }
+
+    match seed.trim().parse::<u64>() {
+        Ok(val) => Ok(Some(val)),
+        Err(_) => Err(format!("Error: The Seed \"{}\" Is Not a Whole Number", seed.trim()))
+    }
+}
+
+fn get_range_text(range: RangeInclusive<u8>) -> String {
+    format!("{} to {}", range.start(), range.end())
+}
+
+fn get_extension(output: &str) -> &'static str {
+    match output {
+        "HTML" => "html",
+        "Text" => "txt",
+        "Markdown" => "md",
+        _ => "pdf"
+    }
+}
+
+fn get_output_name(output: &str) -> &str {
+    match output {
+        "PDF" => "Pdf",
+        _ => output
     }
 }
 
 #[derive(Clone)]
 enum GenStatus {
     InProgress,
+    Generated, //Waiting for the preview
     Done
 }
 
@@ -885,22 +3146,85 @@ fn set_gen_status(status: GenStatus) {
     }
 }
 
-fn set_err_msgs(msgs: Vec<String>) {
-    match ERR_MSGS.lock() {
-        Ok(mut val) => *val = msgs,
+fn set_results(results: Vec<PuzzleResult>) {
+    match RESULTS.lock() {
+        Ok(mut val) => *val = results,
         Err(_) => {
             thread::sleep(std::time::Duration::from_millis(50));
-            set_err_msgs(msgs);
+            set_results(results);
         }
     }
 }
 
-fn get_err_msgs() -> Vec<String> {
-    match ERR_MSGS.lock() {
+fn get_results() -> Vec<PuzzleResult> {
+    match RESULTS.lock() {
         Ok(val) => val.clone(),
         Err(_) => {
             thread::sleep(std::time::Duration::from_millis(50));
-            get_err_msgs()
+            get_results()
+        }
+    }
+}
+
+fn set_preview(word_search_list: Vec<TitledWordSearch>) {
+    match PREVIEW.lock() {
+        Ok(mut val) => *val = word_search_list,
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            set_preview(word_search_list);
+        }
+    }
+}
+
+fn take_preview() -> Vec<TitledWordSearch> {
+    match PREVIEW.lock() {
+        Ok(mut val) => std::mem::take(&mut *val),
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            take_preview()
+        }
+    }
+}
+
+fn add_result(title: String, path: Option<String>, err_msg: Option<String>) {
+    let mut results = get_results();
+    results.push(PuzzleResult {
+        title,
+        path,
+        err_msg
+    });
+    set_results(results);
+}
+
+fn get_key_message(event: Event, _status: iced_native::event::Status) -> Option<Message> {
+    match event {
+        Event::Keyboard(iced::keyboard::Event::KeyPressed { key_code, modifiers }) => match key_code {
+            KeyCode::Tab | KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Escape | KeyCode::S => {
+                let key_code = if key_code == KeyCode::NumpadEnter { KeyCode::Enter } else { key_code };
+                Some(Message::KeyPressed((key_code, modifiers)))
+            },
+            _ => None
+        },
+        _ => None
+    }
+}
+
+fn add_word_check(check: (usize, String, String)) {
+    match WORD_CHECKS.lock() {
+        Ok(mut val) => val.push(check),
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            add_word_check(check);
         }
     }
-}
\ No newline at end of file
+}
+
+fn take_word_checks() -> Vec<(usize, String, String)> {
+    match WORD_CHECKS.lock() {
+        Ok(mut val) => std::mem::take(&mut *val),
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            take_word_checks()
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use iced::{ Color, Point, Rectangle, Size, HorizontalAlignment, VerticalAlignment };
+use iced::canvas::{ self, Cursor, Frame, Geometry, LineCap, Path, Program, Stroke };
+
+use crate::word_search::{ WordSearch, PuzzleKind };
+
+const LINE_COLOR: Color = Color::from_rgb(
+    0xD2 as f32 / 255.0,
+    0xD2 as f32 / 255.0,
+    0xD2 as f32 / 255.0
+);
+
+//Draws the grid of a generated puzzle the way it is printed, with the answers filled in
+pub struct GridPreview {
+    word_search: WordSearch
+}
+
+impl GridPreview {
+    pub fn new(word_search: &WordSearch) -> GridPreview {
+        GridPreview {
+            word_search: word_search.clone()
+        }
+    }
+}
+
+impl<Message> Program<Message> for GridPreview {
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let field = &self.word_search.field;
+        let w = field.len() as f32;
+        let h = field[0].len() as f32;
+        let space = (bounds.width / w).min(bounds.height / h);
+        let left = (bounds.width - space * w) / 2.0;
+
+        frame.fill_rectangle(Point::new(left, 0.0), Size::new(space * w, space * h), Color::WHITE);
+
+        if self.word_search.kind == PuzzleKind::WordSearch { //Mark the words like the answer key does
+            let stroke = Stroke {
+                color: LINE_COLOR,
+                width: space * 0.7,
+                line_cap: LineCap::Round,
+                ..Stroke::default()
+            };
+            for placement in &self.word_search.placements {
+                let cells = placement.cells();
+                let (start_x, start_y) = cells[0];
+                let (end_x, end_y) = cells[cells.len() - 1];
+                let line = Path::line(
+                    Point::new(left + (start_x as f32 + 0.5) * space, (start_y as f32 + 0.5) * space),
+                    Point::new(left + (end_x as f32 + 0.5) * space, (end_y as f32 + 0.5) * space)
+                );
+                frame.stroke(&line, stroke);
+            }
+        }
+
+        for (x, column) in field.iter().enumerate() {
+            for (y, letter) in column.iter().enumerate() {
+                let corner = Point::new(left + (x as f32) * space, (y as f32) * space);
+                if self.word_search.kind != PuzzleKind::WordSearch {
+                    if *letter != (0 as char) {
+                        frame.stroke(&Path::rectangle(corner, Size::new(space, space)), Stroke::default());
+                    }
+                    else if self.word_search.kind == PuzzleKind::FillIn {
+                        frame.fill_rectangle(corner, Size::new(space, space), Color::BLACK);
+                    }
+                }
+                if *letter == (0 as char) {
+                    continue;
+                }
+
+                frame.fill_text(canvas::Text {
+                    content: letter.to_string(),
+                    position: Point::new(corner.x + space / 2.0, corner.y + space / 2.0),
+                    color: Color::BLACK,
+                    size: space * 0.6,
+                    horizontal_alignment: HorizontalAlignment::Center,
+                    vertical_alignment: VerticalAlignment::Center,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
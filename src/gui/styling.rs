@@ -1,320 +1,655 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use serde::Deserialize;
 use iced::widget::{
     button,
     text_input,
     container,
     pick_list,
+    checkbox,
 };
+use iced::Color;
 
+use crate::assets;
+
+static THEME_DIRECTORY: &str = "themes";
+
+lazy_static! {
+    static ref BUILTIN_THEMES: Vec<(String, Theme)> = vec![
+        (String::from("Light"), light()),
+        (String::from("Dark"), dark()),
+        (String::from("High Contrast"), high_contrast())
+    ];
+
+    static ref SYSTEM_DARK: Mutex<Option<bool>> = { //None until the desktop has been asked
+        Mutex::new(None)
+    };
+}
+
+pub static SYSTEM_THEME: &str = "System";
+
+//Colors of every widget, the same stylesheets are used for all themes
 #[derive(Clone)]
-pub enum Theme {
-    Light,
-    Dark
+pub struct Theme {
+    background: Color,
+    text: Color,
+    button: ButtonColors,
+    input: InputColors,
+    pick_list: PickListColors,
+    checkbox: CheckboxColors,
+    border_radius: f32,
+    border_width: f32
 }
 
-impl From<Theme> for Box<dyn button::StyleSheet> {
-    fn from(theme: Theme) -> Self { 
-        match theme {
-            Theme::Light => light::Button.into(),
-            Theme::Dark => dark::Button.into()
+#[derive(Clone, Copy)]
+struct ButtonColors {
+    background: Color,
+    hovered: Color,
+    text: Color,
+    border: Color
+}
+
+#[derive(Clone, Copy)]
+struct InputColors {
+    background: Color,
+    text: Color,
+    placeholder: Color,
+    selection: Color,
+    border: Color,
+    focused: Color //Border of the input that is typed into
+}
+
+#[derive(Clone, Copy)]
+struct PickListColors {
+    background: Color,
+    hovered: Color,
+    text: Color,
+    border: Color,
+    selected_text: Color,
+    selected_background: Color
+}
+
+#[derive(Clone, Copy)]
+struct CheckboxColors {
+    background: Color,
+    hovered: Color,
+    checkmark: Color,
+    border: Color
+}
+
+//Returns the built in themes followed by the theme files in data/themes
+pub fn get_theme_names() -> Vec<String> {
+    let mut names: Vec<String> = vec![String::from(SYSTEM_THEME)];
+    for (name, _) in &*BUILTIN_THEMES {
+        names.push(name.clone());
+    }
+
+    let mut files: Vec<String> = Vec::new();
+    for path in assets::list_files(THEME_DIRECTORY, &["json"]) {
+        if load_theme_file(&path).is_none() { //Broken files aren't offered
+            continue;
+        }
+        if let Some(stem) = path.file_stem() {
+            files.push(stem.to_string_lossy().to_string());
         }
     }
+    files.sort();
+    names.append(&mut files);
+
+    names
 }
 
-impl From<Theme> for Box<dyn text_input::StyleSheet> {
-    fn from(theme: Theme) -> Self { 
-        match theme {
-            Theme::Light => light::TextInput.into(),
-            Theme::Dark => dark::TextInput.into()
+//Unknown themes and theme files that can't be read fall back to the light theme
+pub fn load_theme(name: &str) -> Theme {
+    if name == SYSTEM_THEME {
+        return if system_prefers_dark() { dark() } else { light() };
+    }
+    for (builtin_name, theme) in &*BUILTIN_THEMES {
+        if name == builtin_name {
+            return theme.clone();
         }
     }
-}
 
-impl From<Theme> for Box<dyn container::StyleSheet> {
-    fn from(theme: Theme) -> Self { 
-        match theme {
-            Theme::Light => light::Container.into(),
-            Theme::Dark => dark::Container.into()
+    let path = match assets::find_file(THEME_DIRECTORY, &format!("{}.json", name)) {
+        Some(val) => val,
+        None => {
+            log::warn!("The theme \"{}\" could not be found, Light is used instead", name);
+            return light();
+        }
+    };
+    match load_theme_file(&path) {
+        Some(val) => val,
+        None => {
+            log::warn!("The theme \"{}\" could not be loaded, Light is used instead", path.display());
+            light()
         }
     }
 }
 
-impl From<Theme> for Box<dyn pick_list::StyleSheet> {
-    fn from(theme: Theme) -> Self { 
-        match theme {
-            Theme::Light => light::PickList.into(),
-            Theme::Dark => dark::PickList.into()
-        }
+//The desktop's light/dark preference, asked once when it is first needed and then by update_system_dark
+pub fn system_prefers_dark() -> bool {
+    if let Some(val) = get_system_dark() {
+        return val;
     }
+
+    let dark = read_system_dark();
+    set_system_dark(dark);
+
+    dark
 }
 
-mod dark {
-    use iced::widget::{
-        button,
-        text_input,
-        container,
-        pick_list,
-    };
-    use iced::Color;
-
-    const BACKGROUND: Color = Color::from_rgb(
-        0x2C as f32 / 255.0,
-        0x2C as f32 / 255.0,
-        0x2C as f32 / 255.0,
-    );
-    
-    const ACCENT: Color = Color::from_rgb(
-        0xD0 as f32 / 255.0,
-        0xA4 as f32 / 255.0,
-        0xFF as f32 / 255.0
-    );
-    
-    const CONTRAST: Color = Color::from_rgb(
-        0xB0 as f32 / 255.0,
-        0xD9 as f32 / 255.0,
-        0xDF as f32 / 255.0
-    );
-    
-    const CONTRAST_LIGHTER: Color = Color::from_rgb(
-        0xC8 as f32 / 255.0,
-        0xC7 as f32 / 255.0,
-        0xF7 as f32 / 255.0,
-    );
-    
-    const WIDGET_BACKGROUND: Color = Color::from_rgb(
-        0x40 as f32 / 255.0,
-        0x40 as f32 / 255.0,
-        0x40 as f32 / 255.0,
-    );
-    
-    const WIDGET_HIGHLIGHT: Color = Color::from_rgb(
-        0x35 as f32 / 255.0,
-        0x35 as f32 / 255.0,
-        0x35 as f32 / 255.0,
-    );
-    
-    const WIDGET_LIGHTER: Color = Color::from_rgb(
-        0x99 as f32 / 255.0,
-        0x99 as f32 / 255.0,
-        0x99 as f32 / 255.0,
-    );
-    
-    const WIDGET_TEXT: Color = Color::from_rgb(
-        0xC5 as f32 / 255.0,
-        0xB5 as f32 / 255.0,
-        0xFB as f32 / 255.0
-    );
-
-    pub(super) struct Button;
-    pub(super) struct TextInput;
-    pub(super) struct Container;
-    pub(super) struct PickList;
-
-    impl button::StyleSheet for Button {
-        fn active(&self) -> button::Style {
-            button::Style {
-                background: WIDGET_BACKGROUND.into(),
-                border_radius: 5.0,
-                border_color: ACCENT,
-                border_width: 1.0,
-                text_color: WIDGET_TEXT,
-                ..button::Style::default()
-            }
-        }
+//Asks the desktop again on another thread, since the commands can take up to a second
+pub fn update_system_dark() {
+    thread::spawn(|| set_system_dark(read_system_dark()));
+}
 
-        fn hovered(&self) -> button::Style {
-            button::Style {
-                background: WIDGET_HIGHLIGHT.into(),
-                border_color: ACCENT,
-                border_radius: 5.0,
-                border_width: 1.0,
-                text_color: WIDGET_TEXT,
-                ..button::Style::default()
-            }
-        }
+//Asks the freedesktop portal first, then the GNOME settings and last the name of the GTK theme
+fn read_system_dark() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
     }
 
-    impl container::StyleSheet for Container {
-        fn style(&self) -> container::Style {
-            container::Style {
-                background: BACKGROUND.into(),
-                text_color: CONTRAST.into(),
-                ..container::Style::default()
-            }
+    let portal = run_command("gdbus", &[
+        "call", "--session", "--timeout", "1",
+        "--dest", "org.freedesktop.portal.Desktop",
+        "--object-path", "/org/freedesktop/portal/desktop",
+        "--method", "org.freedesktop.portal.Settings.Read",
+        "org.freedesktop.appearance", "color-scheme"
+    ]);
+    if let Some(output) = portal { //1 prefers dark, 2 prefers light and 0 has no preference
+        if output.contains("uint32 1") {
+            return true;
+        }
+        if output.contains("uint32 2") {
+            return false;
         }
     }
 
-    impl text_input::StyleSheet for TextInput {
-        fn active(&self) -> text_input::Style {
-            text_input::Style {
-                background: WIDGET_LIGHTER.into(),
-                border_radius: 2.0,
-                border_width: 0.0,
-                border_color: ACCENT,
-            }
+    if let Some(output) = run_command("gsettings", &["get", "org.gnome.desktop.interface", "color-scheme"]) {
+        if output.contains("prefer-dark") {
+            return true;
         }
-
-        fn placeholder_color(&self) -> Color {
-            CONTRAST_LIGHTER
+        if output.contains("prefer-light") {
+            return false;
         }
+    }
 
-        fn value_color(&self) -> Color {
-            Color::from_rgb(
-                0x58 as f32 / 255.0,
-                0x57 as f32 / 255.0,
-                0x87 as f32 / 255.0,
-            )
-        }
+    let gtk_theme = match std::env::var("GTK_THEME") {
+        Ok(val) => Some(val),
+        Err(_) => run_command("gsettings", &["get", "org.gnome.desktop.interface", "gtk-theme"])
+    };
+    match gtk_theme {
+        Some(val) => val.to_lowercase().contains("dark"),
+        None => false
+    }
+}
 
-        fn selection_color(&self) -> Color {
-            Color::WHITE
-        }
+fn run_command(program: &str, args: &[&str]) -> Option<String> {
+    match Command::new(program).args(args).output() {
+        Ok(val) if val.status.success() => Some(String::from_utf8_lossy(&val.stdout).to_string()),
+        _ => None
+    }
+}
 
-        fn focused(&self) -> text_input::Style {
-            self.active()
+fn set_system_dark(dark: bool) {
+    match SYSTEM_DARK.lock() {
+        Ok(mut val) => *val = Some(dark),
+        Err(_) => {
+            thread::sleep(Duration::from_millis(50));
+            set_system_dark(dark);
         }
     }
+}
 
-    impl pick_list::StyleSheet for PickList {
-        fn active(&self) -> pick_list::Style {
-            pick_list::Style {
-                text_color: WIDGET_TEXT,
-                background: WIDGET_BACKGROUND.into(),
-                border_radius: 5.0,
-                border_width: 1.0,
-                border_color: ACCENT,
-                ..pick_list::Style::default()
-            }
+fn get_system_dark() -> Option<bool> {
+    match SYSTEM_DARK.lock() {
+        Ok(val) => *val,
+        Err(_) => {
+            thread::sleep(Duration::from_millis(50));
+            get_system_dark()
         }
+    }
+}
 
-        fn menu(&self) -> pick_list::Menu {
-            pick_list::Menu {
-                text_color: WIDGET_TEXT,
-                background: WIDGET_BACKGROUND.into(),
-                border_width: 0.5,
-                border_color: ACCENT,
-                selected_text_color: CONTRAST,
-                selected_background: WIDGET_HIGHLIGHT.into()
-            }
+fn load_theme_file(path: &Path) -> Option<Theme> {
+    let data = match fs::read_to_string(path) {
+        Ok(val) => val,
+        Err(_) => return None
+    };
+    let file: ThemeFile = match serde_json::from_str(&data) {
+        Ok(val) => val,
+        Err(err) => {
+            log::warn!("The theme \"{}\" could not be read: {}", path.display(), err);
+            return None;
         }
+    };
+
+    file.to_theme()
+}
+
+//Colors are written as "#RRGGBB" or "#RRGGBBAA"
+fn parse_color(text: &str) -> Option<Color> {
+    let hex = text.trim().trim_start_matches('#');
+    if (hex.len() != 6 && hex.len() != 8) || !hex.is_ascii() {
+        return None;
+    }
 
-        fn hovered(&self) -> pick_list::Style {
-            self.active()
+    let mut channels: Vec<f32> = Vec::new();
+    for i in (0..hex.len()).step_by(2) {
+        match u8::from_str_radix(&hex[i..i + 2], 16) {
+            Ok(val) => channels.push(val as f32 / 255.0),
+            Err(_) => return None
         }
     }
+    let alpha = if channels.len() == 4 { channels[3] } else { 1.0 };
+
+    Some(Color::from_rgba(channels[0], channels[1], channels[2], alpha))
 }
 
-mod light {
-    use iced::widget::{
-        button,
-        text_input,
-        container,
-        pick_list,
-    };
-    use iced::Color;
-    
-    const GRAY: Color = Color::from_rgb(
-        0x99 as f32 / 255.0,
-        0x99 as f32 / 255.0,
-        0x99 as f32 / 255.0
-    );
-
-    const LIGHT_GRAY: Color = Color::from_rgb(
-        0xAA as f32 / 255.0,
-        0xAA as f32 / 255.0,
-        0xAA as f32 / 255.0,
-    );
-
-    const LIGHTEST_GRAY: Color = Color::from_rgb(
-        0xDD as f32 / 255.0,
-        0xDD as f32 / 255.0,
-        0xDD as f32 / 255.0
-    );
-    
-    const SELECTION_BLUE: Color = Color::from_rgb(
-        0xAA as f32 / 255.0,
-        0xAA as f32 / 255.0,
-        0xFF as f32 / 255.0
-    );
-
-    pub(super) struct Button;
-    pub(super) struct TextInput;
-    pub(super) struct Container;
-    pub(super) struct PickList;
-
-    impl button::StyleSheet for Button {
-        fn active(&self) -> button::Style {
-            button::Style {
-                border_radius: 5.0,
-                border_width: 1.0,
-                border_color: GRAY,
-                ..button::Style::default()
-            }
+const fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::from_rgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+}
+
+fn light() -> Theme {
+    Theme {
+        background: Color::WHITE,
+        text: Color::BLACK,
+        button: ButtonColors {
+            background: Color::WHITE,
+            hovered: rgb(0xDD, 0xDD, 0xDD),
+            text: Color::BLACK,
+            border: rgb(0x99, 0x99, 0x99)
+        },
+        input: InputColors {
+            background: Color::WHITE,
+            text: Color::BLACK,
+            placeholder: rgb(0xAA, 0xAA, 0xAA),
+            selection: rgb(0xAA, 0xAA, 0xFF),
+            border: rgb(0x99, 0x99, 0x99),
+            focused: Color::BLACK
+        },
+        pick_list: PickListColors {
+            background: rgb(0xDE, 0xDE, 0xDE),
+            hovered: rgb(0xDE, 0xDE, 0xDE),
+            text: Color::BLACK,
+            border: rgb(0xB3, 0xB3, 0xB3),
+            selected_text: Color::WHITE,
+            selected_background: rgb(0x66, 0x66, 0xFF)
+        },
+        checkbox: CheckboxColors {
+            background: Color::WHITE,
+            hovered: rgb(0xDD, 0xDD, 0xDD),
+            checkmark: Color::BLACK,
+            border: rgb(0x99, 0x99, 0x99)
+        },
+        border_radius: 5.0,
+        border_width: 1.0
+    }
+}
+
+fn dark() -> Theme {
+    let accent = rgb(0xD0, 0xA4, 0xFF);
+    let widget_background = rgb(0x40, 0x40, 0x40);
+    let widget_highlight = rgb(0x35, 0x35, 0x35);
+    let widget_text = rgb(0xC5, 0xB5, 0xFB);
+
+    Theme {
+        background: rgb(0x2C, 0x2C, 0x2C),
+        text: rgb(0xB0, 0xD9, 0xDF),
+        button: ButtonColors {
+            background: widget_background,
+            hovered: widget_highlight,
+            text: widget_text,
+            border: accent
+        },
+        input: InputColors {
+            background: rgb(0x99, 0x99, 0x99),
+            text: rgb(0x58, 0x57, 0x87),
+            placeholder: rgb(0xC8, 0xC7, 0xF7),
+            selection: Color::WHITE,
+            border: accent,
+            focused: accent
+        },
+        pick_list: PickListColors {
+            background: widget_background,
+            hovered: widget_background,
+            text: widget_text,
+            border: accent,
+            selected_text: rgb(0xB0, 0xD9, 0xDF),
+            selected_background: widget_highlight
+        },
+        checkbox: CheckboxColors {
+            background: widget_background,
+            hovered: widget_highlight,
+            checkmark: accent,
+            border: accent
+        },
+        border_radius: 5.0,
+        border_width: 1.0
+    }
+}
+
+//Black and white with yellow borders for low vision
+fn high_contrast() -> Theme {
+    let yellow = rgb(0xFF, 0xFF, 0x00);
+    let highlight = rgb(0x33, 0x33, 0x00);
+
+    Theme {
+        background: Color::BLACK,
+        text: Color::WHITE,
+        button: ButtonColors {
+            background: Color::BLACK,
+            hovered: highlight,
+            text: Color::WHITE,
+            border: yellow
+        },
+        input: InputColors {
+            background: Color::BLACK,
+            text: Color::WHITE,
+            placeholder: rgb(0xBF, 0xBF, 0xBF),
+            selection: highlight,
+            border: Color::WHITE,
+            focused: yellow
+        },
+        pick_list: PickListColors {
+            background: Color::BLACK,
+            hovered: highlight,
+            text: Color::WHITE,
+            border: yellow,
+            selected_text: Color::BLACK,
+            selected_background: yellow
+        },
+        checkbox: CheckboxColors {
+            background: Color::BLACK,
+            hovered: highlight,
+            checkmark: yellow,
+            border: yellow
+        },
+        border_radius: 2.0,
+        border_width: 2.0
+    }
+}
+
+//Theme file format, every color is a hex string
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThemeFile {
+    background: String,
+    text: String,
+    button: ButtonFile,
+    input: InputFile,
+    pick_list: PickListFile,
+    checkbox: CheckboxFile,
+    #[serde(default = "default_border_radius")]
+    border_radius: f32,
+    #[serde(default = "default_border_width")]
+    border_width: f32
+}
+
+#[derive(Deserialize)]
+struct ButtonFile {
+    background: String,
+    hovered: String,
+    text: String,
+    border: String
+}
+
+#[derive(Deserialize)]
+struct InputFile {
+    background: String,
+    text: String,
+    placeholder: String,
+    selection: String,
+    border: String,
+    focused: String
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PickListFile {
+    background: String,
+    hovered: String,
+    text: String,
+    border: String,
+    selected_text: String,
+    selected_background: String
+}
+
+#[derive(Deserialize)]
+struct CheckboxFile {
+    background: String,
+    hovered: String,
+    checkmark: String,
+    border: String
+}
+
+fn default_border_radius() -> f32 {
+    5.0
+}
+
+fn default_border_width() -> f32 {
+    1.0
+}
+
+impl ThemeFile {
+    fn to_theme(&self) -> Option<Theme> {
+        Some(Theme {
+            background: parse_color(&self.background)?,
+            text: parse_color(&self.text)?,
+            button: ButtonColors {
+                background: parse_color(&self.button.background)?,
+                hovered: parse_color(&self.button.hovered)?,
+                text: parse_color(&self.button.text)?,
+                border: parse_color(&self.button.border)?
+            },
+            input: InputColors {
+                background: parse_color(&self.input.background)?,
+                text: parse_color(&self.input.text)?,
+                placeholder: parse_color(&self.input.placeholder)?,
+                selection: parse_color(&self.input.selection)?,
+                border: parse_color(&self.input.border)?,
+                focused: parse_color(&self.input.focused)?
+            },
+            pick_list: PickListColors {
+                background: parse_color(&self.pick_list.background)?,
+                hovered: parse_color(&self.pick_list.hovered)?,
+                text: parse_color(&self.pick_list.text)?,
+                border: parse_color(&self.pick_list.border)?,
+                selected_text: parse_color(&self.pick_list.selected_text)?,
+                selected_background: parse_color(&self.pick_list.selected_background)?
+            },
+            checkbox: CheckboxColors {
+                background: parse_color(&self.checkbox.background)?,
+                hovered: parse_color(&self.checkbox.hovered)?,
+                checkmark: parse_color(&self.checkbox.checkmark)?,
+                border: parse_color(&self.checkbox.border)?
+            },
+            border_radius: self.border_radius,
+            border_width: self.border_width
+        })
+    }
+}
+
+impl From<Theme> for Box<dyn button::StyleSheet> {
+    fn from(theme: Theme) -> Self {
+        Box::new(Button(theme))
+    }
+}
+
+impl From<Theme> for Box<dyn text_input::StyleSheet> {
+    fn from(theme: Theme) -> Self {
+        Box::new(TextInput(theme))
+    }
+}
+
+impl From<Theme> for Box<dyn container::StyleSheet> {
+    fn from(theme: Theme) -> Self {
+        Box::new(Container(theme))
+    }
+}
+
+impl From<Theme> for Box<dyn pick_list::StyleSheet> {
+    fn from(theme: Theme) -> Self {
+        Box::new(PickList(theme))
+    }
+}
+
+impl From<Theme> for Box<dyn checkbox::StyleSheet> {
+    fn from(theme: Theme) -> Self {
+        Box::new(Checkbox(theme))
+    }
+}
+
+struct Button(Theme);
+struct TextInput(Theme);
+struct Container(Theme);
+struct PickList(Theme);
+struct Checkbox(Theme);
+
+impl button::StyleSheet for Button {
+    fn active(&self) -> button::Style {
+        button::Style {
+            background: self.0.button.background.into(),
+            border_radius: self.0.border_radius,
+            border_color: self.0.button.border,
+            border_width: self.0.border_width,
+            text_color: self.0.button.text,
+            ..button::Style::default()
         }
+    }
 
-        fn hovered(&self) -> button::Style {
-            button::Style {
-                border_radius: 5.0,
-                border_width: 1.0,
-                border_color: GRAY,
-                background: LIGHTEST_GRAY.into(),
-                ..button::Style::default()
-            }
+    fn hovered(&self) -> button::Style {
+        button::Style {
+            background: self.0.button.hovered.into(),
+            ..self.active()
         }
     }
+}
 
-    impl container::StyleSheet for Container {
-        fn style(&self) -> container::Style {
-            container::Style {
-                ..container::Style::default()
-            }
+impl container::StyleSheet for Container {
+    fn style(&self) -> container::Style {
+        container::Style {
+            background: self.0.background.into(),
+            text_color: self.0.text.into(),
+            ..container::Style::default()
         }
     }
+}
 
-    impl text_input::StyleSheet for TextInput {
-        fn active(&self) -> text_input::Style {
-            text_input::Style {
-                border_radius: 2.0,
-                border_width: 2.0,
-                border_color: GRAY,
-                ..text_input::Style::default()
-            }
+impl text_input::StyleSheet for TextInput {
+    fn active(&self) -> text_input::Style {
+        text_input::Style {
+            background: self.0.input.background.into(),
+            border_radius: self.0.border_radius,
+            border_width: self.0.border_width,
+            border_color: self.0.input.border
         }
+    }
+
+    fn placeholder_color(&self) -> Color {
+        self.0.input.placeholder
+    }
+
+    fn value_color(&self) -> Color {
+        self.0.input.text
+    }
 
-        fn placeholder_color(&self) -> Color {
-            LIGHT_GRAY
+    fn selection_color(&self) -> Color {
+        self.0.input.selection
+    }
+
+    fn focused(&self) -> text_input::Style { //Shows where typing goes when using Tab
+        text_input::Style {
+            border_width: self.0.border_width + 1.0,
+            border_color: self.0.input.focused,
+            ..self.active()
         }
+    }
+}
 
-        fn value_color(&self) -> Color {
-            Color::BLACK
+impl pick_list::StyleSheet for PickList {
+    fn active(&self) -> pick_list::Style {
+        pick_list::Style {
+            text_color: self.0.pick_list.text,
+            background: self.0.pick_list.background.into(),
+            border_radius: self.0.border_radius,
+            border_width: self.0.border_width,
+            border_color: self.0.pick_list.border,
+            ..pick_list::Style::default()
         }
+    }
 
-        fn selection_color(&self) -> Color {
-            SELECTION_BLUE
+    fn menu(&self) -> pick_list::Menu {
+        pick_list::Menu {
+            text_color: self.0.pick_list.text,
+            background: self.0.pick_list.background.into(),
+            border_width: self.0.border_width / 2.0,
+            border_color: self.0.pick_list.border,
+            selected_text_color: self.0.pick_list.selected_text,
+            selected_background: self.0.pick_list.selected_background.into()
         }
+    }
 
-        fn focused(&self) -> text_input::Style {
-            self.active()
+    fn hovered(&self) -> pick_list::Style {
+        pick_list::Style {
+            background: self.0.pick_list.hovered.into(),
+            ..self.active()
         }
     }
+}
 
-    impl pick_list::StyleSheet for PickList {
-        fn active(&self) -> pick_list::Style {
-            pick_list::Style {
-                border_radius: 5.0,
-                border_width: 1.0,
-                ..pick_list::Style::default()
-            }
+impl checkbox::StyleSheet for Checkbox {
+    fn active(&self, _is_checked: bool) -> checkbox::Style {
+        checkbox::Style {
+            background: self.0.checkbox.background.into(),
+            checkmark_color: self.0.checkbox.checkmark,
+            border_radius: self.0.border_radius,
+            border_width: self.0.border_width,
+            border_color: self.0.checkbox.border
         }
+    }
 
-        fn menu(&self) -> pick_list::Menu {
-            pick_list::Menu {
-                border_width: 0.5,
-                ..pick_list::Menu::default()
-            }
+    fn hovered(&self, is_checked: bool) -> checkbox::Style {
+        checkbox::Style {
+            background: self.0.checkbox.hovered.into(),
+            ..self.active(is_checked)
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        fn hovered(&self) -> pick_list::Style {
-            self.active()
+    #[test]
+    fn colors_are_hex_with_optional_alpha() {
+        assert_eq!(parse_color("#FF0000"), Some(Color::from_rgb(1.0, 0.0, 0.0)));
+        assert_eq!(parse_color(" 00ff0080 "), Some(Color::from_rgba(0.0, 1.0, 0.0, 128.0 / 255.0)));
+        for text in ["", "#FFF", "#GG0000", "#FF00000", "#ÄÖÜ"].iter() {
+            assert!(parse_color(text).is_none(), "\"{}\" was accepted", text);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn theme_files_need_every_color() {
+        let theme = load_theme_file(&Path::new("data").join(THEME_DIRECTORY).join("Ocean.json")).unwrap();
+        assert_eq!(theme.background, parse_color("#0B2A3C").unwrap());
+        assert_eq!(theme.border_radius, 5.0);
+
+        let data = fs::read_to_string(Path::new("data").join(THEME_DIRECTORY).join("Ocean.json")).unwrap();
+        let broken = data.replacen("\"#0B2A3C\"", "\"blue\"", 1);
+        assert!(serde_json::from_str::<ThemeFile>(&broken).unwrap().to_theme().is_none());
+        let missing = data.replacen("\"background\"", "\"backdrop\"", 1);
+        assert!(serde_json::from_str::<ThemeFile>(&missing).is_err());
+    }
+
+    #[test]
+    fn unknown_themes_fall_back_to_light() {
+        assert_eq!(load_theme("No Such Theme").background, light().background);
+        assert_ne!(light().background, dark().background);
+    }
+}
@@ -0,0 +1,295 @@
+use std::fs::File;
+use std::io::{ Error, ErrorKind, Write };
+use serde::Serialize;
+
+use crate::request::TitledWordSearch;
+use crate::word_search::{ PuzzleKind, MESSAGE_NOTE };
+
+static STYLE: &str = r#"
+body { font-family: "Times New Roman", serif; background: #FFFFFF; color: #000000; margin: 0; padding: 20px; }
+.puzzle { max-width: 700px; margin: 0 auto 60px auto; text-align: center; }
+.puzzle h1 { font-size: 32px; margin-bottom: 20px; }
+.subtitle { font-size: 18px; margin-top: -10px; margin-bottom: 20px; }
+.grid { border: 2px solid #000000; border-collapse: collapse; margin: 0 auto; touch-action: none; user-select: none; -webkit-user-select: none; }
+.grid td { width: 36px; height: 36px; font-size: 24px; text-align: center; cursor: pointer; }
+.grid td.selected { background: #AAAAFF; }
+.grid td.found { background: #B0D9DF; }
+.grid td.answer { outline: 2px solid #D0A4FF; outline-offset: -3px; }
+.grid td.leftover { color: #C00000; font-weight: bold; }
+.words { list-style: none; padding: 0; margin: 20px auto; columns: 3; max-width: 600px; text-align: left; font-size: 18px; }
+.words li.found { text-decoration: line-through; color: #999999; }
+.status { min-height: 24px; font-size: 20px; }
+.note { font-style: italic; }
+.message { font-size: 20px; font-weight: bold; }
+button { font-size: 16px; margin: 0 5px; padding: 4px 12px; border: 1px solid #999999; border-radius: 5px; background: #FFFFFF; cursor: pointer; }
+button:hover { background: #DDDDDD; }
+"#;
+
+static SCRIPT: &str = r#"
+(function() {
+    var puzzles = document.querySelectorAll(".puzzle");
+    for (var i = 0; i < puzzles.length; i++) {
+        setup(puzzles[i], PUZZLES[i]);
+    }
+
+    function setup(puzzle, data) {
+        var grid = puzzle.querySelector(".grid");
+        var status = puzzle.querySelector(".status");
+        var items = puzzle.querySelectorAll(".words li");
+        var message = puzzle.querySelector(".message");
+        var found = [];
+        var start = null;
+        var selection = [];
+        var shown = false; //Crossing words share cells, so toggling per word would unmark them again
+
+        function getCell(x, y) {
+            return grid.querySelector('td[data-x="' + x + '"][data-y="' + y + '"]');
+        }
+
+        function getPos(element) {
+            var cell = element ? element.closest("td") : null;
+            if (!cell || !grid.contains(cell)) {
+                return null;
+            }
+            return [parseInt(cell.dataset.x), parseInt(cell.dataset.y)];
+        }
+
+        function getLine(from, to) { //Only straight lines (horizontal, vertical and diagonal) can be selected
+            var dx = to[0] - from[0];
+            var dy = to[1] - from[1];
+            if (dx !== 0 && dy !== 0 && Math.abs(dx) !== Math.abs(dy)) {
+                return [];
+            }
+            var steps = Math.max(Math.abs(dx), Math.abs(dy));
+            var line = [];
+            for (var i = 0; i <= steps; i++) {
+                line.push([from[0] + i * Math.sign(dx), from[1] + i * Math.sign(dy)]);
+            }
+            return line;
+        }
+
+        function setSelection(cells) {
+            selection.forEach(function(pos) { getCell(pos[0], pos[1]).classList.remove("selected"); });
+            selection = cells;
+            selection.forEach(function(pos) { getCell(pos[0], pos[1]).classList.add("selected"); });
+        }
+
+        function sameCells(a, b) {
+            if (a.length !== b.length) {
+                return false;
+            }
+            for (var i = 0; i < a.length; i++) {
+                if (a[i][0] !== b[i][0] || a[i][1] !== b[i][1]) {
+                    return false;
+                }
+            }
+            return true;
+        }
+
+        function checkSelection() {
+            var reversed = selection.slice().reverse();
+            for (var i = 0; i < data.words.length; i++) {
+                var word = data.words[i];
+                if (found[i] || !(sameCells(word.cells, selection) || sameCells(word.cells, reversed))) {
+                    continue;
+                }
+
+                found[i] = true;
+                word.cells.forEach(function(pos) { getCell(pos[0], pos[1]).classList.add("found"); });
+                items[i].classList.add("found");
+
+                var count = found.filter(Boolean).length;
+                if (count === data.words.length) {
+                    status.textContent = "All words found!";
+                    if (data.message) { //The letters that are left over spell the hidden message
+                        status.textContent += " Now read the remaining letters.";
+                        grid.querySelectorAll("td:not(.found)").forEach(function(cell) { cell.classList.add("leftover"); });
+                    }
+                }
+                else {
+                    status.textContent = count + " of " + data.words.length + " words found";
+                }
+                return;
+            }
+        }
+
+        grid.addEventListener("pointerdown", function(event) {
+            start = getPos(event.target);
+            if (start) {
+                event.preventDefault();
+                setSelection([start]);
+            }
+        });
+
+        document.addEventListener("pointermove", function(event) {
+            if (!start) {
+                return;
+            }
+            var pos = getPos(document.elementFromPoint(event.clientX, event.clientY));
+            if (pos) {
+                var line = getLine(start, pos);
+                if (line.length > 0) {
+                    setSelection(line);
+                }
+            }
+        });
+
+        document.addEventListener("pointerup", function() {
+            if (!start) {
+                return;
+            }
+            checkSelection();
+            setSelection([]);
+            start = null;
+        });
+
+        puzzle.querySelector(".show-answers").addEventListener("click", function() {
+            shown = !shown;
+            data.words.forEach(function(word) {
+                word.cells.forEach(function(pos) { getCell(pos[0], pos[1]).classList.toggle("answer", shown); });
+            });
+            if (message) {
+                message.textContent = shown ? "Hidden message: " + data.message : "";
+            }
+        });
+
+        puzzle.querySelector(".reset").addEventListener("click", function() {
+            found = [];
+            shown = false;
+            status.textContent = "";
+            grid.querySelectorAll("td").forEach(function(cell) { cell.classList.remove("found", "answer", "leftover"); });
+            if (message) {
+                message.textContent = "";
+            }
+            for (var i = 0; i < items.length; i++) {
+                items[i].classList.remove("found");
+            }
+        });
+    }
+})();
+"#;
+
+pub fn create_html(word_search_list: Vec<TitledWordSearch>, path: &str) -> Result<(), Error> {
+    if word_search_list.iter().any(|word_search| word_search.word_search.kind != PuzzleKind::WordSearch) {
+        return Err(Error::new(ErrorKind::InvalidInput, "Interactive html is only available for word searches"));
+    }
+
+    let mut file = File::create(path)?;
+    file.write_all(get_html(&word_search_list).as_bytes())?;
+
+    Ok(())
+}
+
+fn get_html(word_search_list: &[TitledWordSearch]) -> String {
+    let mut body = String::new();
+    let mut puzzle_data: Vec<PuzzleData> = Vec::new();
+    for word_search in word_search_list {
+        body.push_str(&get_puzzle_html(word_search));
+        puzzle_data.push(get_puzzle_data(word_search));
+    }
+
+    let data = match serde_json::to_string(&puzzle_data) {
+        Ok(val) => val.replace("</", "<\\/"), //Prevent closing the script tag early
+        Err(_) => String::from("[]")
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>Word Searches</title>\n<style>{}</style>\n</head>\n<body>\n{}<script>\nvar PUZZLES = {};\n{}</script>\n</body>\n</html>\n",
+        STYLE, body, data, SCRIPT
+    )
+}
+
+fn get_puzzle_html(word_search: &TitledWordSearch) -> String {
+    let field = &word_search.word_search.field;
+    let mut html = String::from("<section class=\"puzzle\">\n");
+    html.push_str(&format!("<h1>{}</h1>\n", escape(&word_search.title)));
+    if !word_search.subtitle.is_empty() {
+        html.push_str(&format!("<p class=\"subtitle\">{}</p>\n", escape(&word_search.subtitle)));
+    }
+
+    html.push_str("<table class=\"grid\">\n");
+    for y in 0..field[0].len() {
+        html.push_str("<tr>");
+        for (x, column) in field.iter().enumerate() {
+            html.push_str(&format!("<td data-x=\"{}\" data-y=\"{}\">{}</td>", x, y, escape(&column[y].to_string())));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<ul class=\"words\">\n");
+    for placement in &word_search.word_search.placements {
+        html.push_str(&format!("<li>{}</li>\n", escape(&placement.word)));
+    }
+    html.push_str("</ul>\n");
+    if word_search.word_search.message.is_some() {
+        html.push_str(&format!("<p class=\"note\">{}</p>\n<p class=\"message\"></p>\n", MESSAGE_NOTE));
+    }
+
+    html.push_str("<p class=\"status\"></p>\n");
+    html.push_str("<button class=\"show-answers\">Show Answers</button><button class=\"reset\">Reset</button>\n");
+    html.push_str("</section>\n");
+
+    html
+}
+
+fn get_puzzle_data(word_search: &TitledWordSearch) -> PuzzleData {
+    let mut words: Vec<WordData> = Vec::new();
+    for placement in &word_search.word_search.placements {
+        words.push(WordData {
+            word: placement.word.clone(),
+            cells: placement.cells()
+        });
+    }
+
+    PuzzleData {
+        words,
+        message: word_search.word_search.message.clone()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace("&", "&amp;")
+        .replace("<", "&lt;")
+        .replace(">", "&gt;")
+        .replace("\"", "&quot;")
+}
+
+#[derive(Serialize)]
+struct PuzzleData {
+    words: Vec<WordData>,
+    message: Option<String>
+}
+
+#[derive(Serialize)]
+struct WordData {
+    word: String,
+    cells: Vec<(usize, usize)>
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_search::{ WordSearch, Placement };
+
+    #[test]
+    fn cells_of_every_word_are_embedded() {
+        //CAT is reversed along the top row and crosses ANT in its middle letter
+        let word_search = WordSearch {
+            field: vec![vec!['T', 'O', 'E'], vec!['A', 'N', 'T'], vec!['C', 'U', 'P']],
+            word_list: vec![String::from("CAT"), String::from("ANT")],
+            placements: vec![
+                Placement { word: String::from("CAT"), x: 0, y: 0, orientation: 0, length: 3 },
+                Placement { word: String::from("ANT"), x: 1, y: 0, orientation: 1, length: 3 }
+            ],
+            message: Some(String::from("</script>")),
+            kind: PuzzleKind::WordSearch
+        };
+        let html = get_html(&[TitledWordSearch::from_word_search("Animals", word_search)]);
+
+        assert!(html.contains(r#"{"word":"CAT","cells":[[0,0],[1,0],[2,0]]}"#), "{}", html);
+        assert!(html.contains(r#"{"word":"ANT","cells":[[1,0],[1,1],[1,2]]}"#), "{}", html);
+        assert!(html.contains(r#"<td data-x="0" data-y="0">T</td><td data-x="1" data-y="0">A</td><td data-x="2" data-y="0">C</td>"#));
+        assert_eq!(html.matches("</script>").count(), 1);
+    }
+}
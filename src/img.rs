@@ -2,29 +2,37 @@ use std::fs;
 use imagine::png;
 use iced::window::icon::Icon;
 
+use crate::assets;
+
 pub fn get_icon() -> Option<Icon> {
-    let (data, width, height) = match load_image_bytes() {
-        Some(val) => val,
-        None => return None
-    };
+    let (data, width, height) = load_png_bytes(&assets::get_icon())?;
     match Icon::from_rgba(data, width as u32, height as u32) {
         Ok(val) => Some(val),
         Err(err) => {
-            println!("err: {}", err);
+            log::warn!("The icon could not be used: {}", err);
             None
         }
     } 
 }
 
-fn load_image_bytes() -> Option<(Vec<u8>, usize, usize)> {
-    let read_bytes = match fs::read("./data/icon.png") {
+//Returns the pixels as rgba bytes together with the width and height
+pub fn load_png(path: &str) -> Option<(Vec<u8>, usize, usize)> {
+    let read_bytes = match fs::read(path) {
         Ok(val) => val,
-        Err(_) => return None
+        Err(err) => {
+            log::warn!("\"{}\" could not be read: {}", path, err);
+            return None;
+        }
     };
-    let png = match png::parse_png_rgba8(read_bytes.as_slice()) {
+
+    load_png_bytes(&read_bytes)
+}
+
+pub fn load_png_bytes(bytes: &[u8]) -> Option<(Vec<u8>, usize, usize)> {
+    let png = match png::parse_png_rgba8(bytes) {
         Ok(val) => val,
         Err(err) => {
-            println!("Error: {:#?}", err);
+            log::warn!("Not a supported png: {:?}", err);
             return None;
         }
     };
@@ -41,4 +49,56 @@ fn load_image_bytes() -> Option<(Vec<u8>, usize, usize)> {
     Some(
         (new_data, png.bitmap.width(), png.bitmap.height())
     )
+}
+
+//Scales a picture down to at most max_size pixels on its longer side, transparent parts become white
+pub fn load_thumbnail(path: &str, max_size: usize) -> Option<Thumbnail> {
+    let (data, width, height) = load_png(path)?;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let scale = (width.max(height) as f32 / max_size as f32).max(1.0);
+    let thumb_width = ((width as f32 / scale) as usize).max(1);
+    let thumb_height = ((height as f32 / scale) as usize).max(1);
+
+    let mut pixels: Vec<[u8; 3]> = Vec::new();
+    for thumb_y in 0..thumb_height {
+        let start_y = (thumb_y as f32 * scale) as usize;
+        let end_y = (((thumb_y + 1) as f32 * scale) as usize).min(height).max(start_y + 1);
+        for thumb_x in 0..thumb_width {
+            let start_x = (thumb_x as f32 * scale) as usize;
+            let end_x = (((thumb_x + 1) as f32 * scale) as usize).min(width).max(start_x + 1);
+
+            //Average of all pixels covered by the thumbnail pixel
+            let mut sum: [u32; 3] = [0, 0, 0];
+            let mut count = 0;
+            for y in start_y..end_y {
+                for x in start_x..end_x {
+                    let index = (y * width + x) * 4;
+                    let alpha = data[index + 3] as u32;
+                    for c in 0..3 {
+                        sum[c] += (data[index + c] as u32 * alpha + 255 * (255 - alpha)) / 255;
+                    }
+                    count += 1;
+                }
+            }
+            pixels.push([(sum[0] / count) as u8, (sum[1] / count) as u8, (sum[2] / count) as u8]);
+        }
+    }
+
+    Some(
+        Thumbnail {
+            pixels,
+            width: thumb_width,
+            height: thumb_height
+        }
+    )
+}
+
+#[derive(Clone)]
+pub struct Thumbnail {
+    pub pixels: Vec<[u8; 3]>, //Rows from top to bottom
+    pub width: usize,
+    pub height: usize
 }
\ No newline at end of file
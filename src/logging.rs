@@ -0,0 +1,231 @@
+use std::fs::{ self, File, OpenOptions };
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use log::{ Log, Metadata, Record, LevelFilter };
+use chrono::Local;
+
+static LOGGER: Logger = Logger;
+static LEVEL_VARIABLE: &str = "WORDSEARCH_LOG";
+static APP_DIRECTORY: &str = "wordsearch";
+static FILE_NAME: &str = "wordsearch";
+const MAX_SIZE: u64 = 1024 * 1024; //Full files are renamed to wordsearch.1.log, wordsearch.2.log and then removed
+const OLD_FILES: usize = 2;
+
+lazy_static! {
+    static ref LOG_FILE: Mutex<Option<LogFile>> = { //None if the file can't be written
+        Mutex::new(None)
+    };
+
+    static ref LEVELS: Mutex<(LevelFilter, LevelFilter)> = { //Shown on stderr and written to the log file
+        Mutex::new((LevelFilter::Warn, LevelFilter::Info))
+    };
+}
+
+//Warnings and errors are shown on stderr unless WORDSEARCH_LOG is set to another level
+//(off, error, warn, info, debug or trace). The log file gets everything from info on, or debug in verbose mode
+pub fn init() {
+    let stderr_level = match std::env::var(LEVEL_VARIABLE) {
+        Ok(val) => val.parse::<LevelFilter>().unwrap_or(LevelFilter::Warn),
+        Err(_) => LevelFilter::Warn
+    };
+    set_levels((stderr_level, LevelFilter::Info));
+
+    if let Some(path) = get_log_path() {
+        set_log_file(LogFile::open(path));
+    }
+    if log::set_logger(&LOGGER).is_err() {
+        return;
+    }
+    update_max_level();
+
+    //Panics of the generation and export threads would otherwise only leave the progress bar hanging
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("Panic in thread \"{}\": {}", thread::current().name().unwrap_or("unnamed"), info);
+        default_hook(info);
+    }));
+
+    log::info!("Started wordsearch {} on {}", env!("CARGO_PKG_VERSION"), std::env::consts::OS);
+}
+
+//Verbose mode writes the queries, every generation attempt and words that couldn't be placed to the log file
+pub fn set_verbose(verbose: bool) {
+    let (stderr_level, _) = get_levels();
+    let file_level = if verbose { LevelFilter::Debug } else { LevelFilter::Info };
+    set_levels((stderr_level, file_level));
+    update_max_level();
+}
+
+pub fn set_stderr_level(level: LevelFilter) {
+    let (_, file_level) = get_levels();
+    set_levels((level, file_level));
+    update_max_level();
+}
+
+//e.g. ~/.local/share/wordsearch/logs on Linux
+pub fn get_log_directory() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(APP_DIRECTORY).join("logs"))
+}
+
+fn get_log_path() -> Option<PathBuf> {
+    get_log_directory().map(|dir| dir.join(format!("{}.log", FILE_NAME)))
+}
+
+fn update_max_level() {
+    let (stderr_level, file_level) = get_levels();
+    log::set_max_level(stderr_level.max(file_level));
+}
+
+fn get_levels() -> (LevelFilter, LevelFilter) {
+    match LEVELS.lock() {
+        Ok(val) => *val,
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            get_levels()
+        }
+    }
+}
+
+fn set_levels(levels: (LevelFilter, LevelFilter)) {
+    match LEVELS.lock() {
+        Ok(mut val) => *val = levels,
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            set_levels(levels);
+        }
+    }
+}
+
+fn set_log_file(log_file: Option<LogFile>) {
+    match LOG_FILE.lock() {
+        Ok(mut val) => *val = log_file,
+        Err(_) => {
+            thread::sleep(std::time::Duration::from_millis(50));
+            set_log_file(log_file);
+        }
+    }
+}
+
+struct Logger;
+
+struct LogFile {
+    file: File,
+    path: PathBuf,
+    size: u64
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool { //The libraries log a lot about rendering and connections
+        metadata.level() <= log::max_level() && metadata.target().starts_with("wordsearch")
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let (stderr_level, file_level) = get_levels();
+        let time = Local::now();
+        if record.level() <= stderr_level {
+            eprintln!("{} {:<5} {}: {}", time.format("%H:%M:%S"), record.level(), record.target(), record.args());
+        }
+        if record.level() <= file_level {
+            let line = format!(
+                "{} {:<5} [{}] {}: {}\n",
+                time.format("%Y-%m-%d %H:%M:%S%.3f"), record.level(), thread::current().name().unwrap_or("unnamed"), record.target(), record.args()
+            );
+            if let Ok(mut log_file) = LOG_FILE.lock() { //A broken log must never stop the program
+                if let Some(val) = log_file.as_mut() {
+                    val.write(&line);
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut log_file) = LOG_FILE.lock() {
+            if let Some(val) = log_file.as_mut() {
+                let _ = val.file.flush();
+            }
+        }
+    }
+}
+
+impl LogFile {
+    fn open(path: PathBuf) -> Option<LogFile> {
+        if let Some(directory) = path.parent() {
+            if fs::create_dir_all(directory).is_err() {
+                return None;
+            }
+        }
+        let file = match OpenOptions::new().create(true).append(true).open(&path) {
+            Ok(val) => val,
+            Err(_) => return None
+        };
+        let size = match file.metadata() {
+            Ok(val) => val.len(),
+            Err(_) => 0
+        };
+
+        Some(
+            LogFile {
+                file,
+                path,
+                size
+            }
+        )
+    }
+
+    fn write(&mut self, line: &str) {
+        if self.size + line.len() as u64 > MAX_SIZE {
+            self.rotate();
+        }
+        if self.file.write_all(line.as_bytes()).is_ok() {
+            self.size += line.len() as u64;
+        }
+    }
+
+    //wordsearch.log becomes wordsearch.1.log, the oldest file is removed
+    fn rotate(&mut self) {
+        let get_old_path = |number: usize| self.path.with_file_name(format!("{}.{}.log", FILE_NAME, number));
+        let _ = fs::remove_file(get_old_path(OLD_FILES));
+        for number in (1..OLD_FILES).rev() {
+            let _ = fs::rename(get_old_path(number), get_old_path(number + 1));
+        }
+        let _ = fs::rename(&self.path, get_old_path(1));
+
+        if let Ok(file) = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path) {
+            self.file = file;
+            self.size = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_files_are_kept_twice() {
+        let directory = std::env::temp_dir().join(format!("wordsearch_logs_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let path = directory.join(format!("{}.log", FILE_NAME));
+        let mut log_file = LogFile::open(path.clone()).unwrap();
+
+        //Two of these lines don't fit into one file
+        let lines: Vec<String> = "ABCD".chars().map(|letter| letter.to_string().repeat(MAX_SIZE as usize / 2 + 1)).collect();
+        for line in &lines {
+            log_file.write(line);
+        }
+        let read = |name: &str| fs::read_to_string(directory.join(name)).ok();
+        let contents = (read("wordsearch.log"), read("wordsearch.1.log"), read("wordsearch.2.log"), read("wordsearch.3.log"));
+        let _ = fs::remove_dir_all(&directory);
+
+        assert_eq!(contents.0.as_ref(), Some(&lines[3]));
+        assert_eq!(contents.1.as_ref(), Some(&lines[2]));
+        assert_eq!(contents.2.as_ref(), Some(&lines[1]));
+        assert!(contents.3.is_none());
+    }
+}
@@ -7,6 +7,8 @@ extern crate serde_json;
 extern crate pdf_canvas;
 extern crate iced;
 extern crate imagine;
+extern crate chrono;
+extern crate ttf_parser;
 
 #[macro_use]
 extern crate lazy_static;
@@ -18,10 +20,25 @@ mod pdf;
 mod gui;
 mod config;
 mod img;
+mod html;
+mod text;
+mod cli;
+mod fonts;
+mod project;
+mod output;
+mod assets;
+mod logging;
 
 fn main() {
+    logging::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(args));
+    }
+
     match gui::run() {
         Ok(_) => (),
-        Err(_) => ()
+        Err(err) => log::error!("The window could not be opened: {}", err)
     };
 }
\ No newline at end of file
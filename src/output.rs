@@ -0,0 +1,151 @@
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+use chrono::{ DateTime, Local };
+
+use crate::request::TitledWordSearch;
+
+static DEFAULT_NAME: &str = "wordsearch";
+
+//Creates the directory if it is missing and splits the word searches into the files to write.
+//Taken file names get a number added unless they may be overwritten
+pub fn get_files(word_search_list: Vec<TitledWordSearch>, options: &FileOptions, extension: &str) -> Result<Vec<(String, Vec<TitledWordSearch>)>, Error> {
+    fs::create_dir_all(&options.directory)?;
+
+    let titles: Vec<String> = word_search_list.iter().map(|word_search| word_search.title.clone()).collect();
+    let paths = get_paths(&titles, options, extension);
+    let groups: Vec<Vec<TitledWordSearch>> = if options.per_puzzle {
+        word_search_list.into_iter().map(|word_search| vec![word_search]).collect()
+    }
+    else {
+        vec![word_search_list]
+    };
+
+    Ok(paths.into_iter().zip(groups).collect())
+}
+
+//Files that already exist and would be replaced, so the user can be asked first
+pub fn get_conflicts(titles: &[String], options: &FileOptions, extension: &str) -> Vec<String> {
+    let options = FileOptions {
+        overwrite: true,
+        ..options.clone()
+    };
+
+    get_paths(titles, &options, extension).into_iter().filter(|path| Path::new(path).exists()).collect()
+}
+
+//Checks that files can be written to the directory. Missing directories are fine if they can be created
+pub fn check_directory(directory: &str) -> Result<(), String> {
+    if directory.trim() == "" {
+        return Err(String::from("No Saving Directory Has Been Specified"));
+    }
+
+    let mut path = Path::new(directory);
+    while !path.exists() { //The closest directory that exists has to allow creating the rest
+        path = match path.parent() {
+            Some(val) if val != Path::new("") => val,
+            _ => Path::new(".")
+        };
+    }
+    if !path.is_dir() {
+        return Err(format!("\"{}\" is Not a Directory", path.display()));
+    }
+
+    let test_path = path.join(".wordsearch_write_test");
+    match fs::write(&test_path, "") {
+        Ok(_) => {
+            let _ = fs::remove_file(&test_path);
+            Ok(())
+        },
+        Err(_) => Err(format!("\"{}\" is Not Writable", path.display()))
+    }
+}
+
+//Replaces the placeholders {date}, {time}, {title} and {n} and leaves out characters that can't be part of a file name
+pub fn fill_template(template: &str, title: &str, number: usize, time: &DateTime<Local>) -> String {
+    let name = template.replace("{date}", &time.format("%Y-%m-%d").to_string())
+        .replace("{time}", &time.format("%H-%M-%S").to_string())
+        .replace("{title}", title)
+        .replace("{n}", &number.to_string());
+    let name: String = name.chars().filter(|c| !"/\\:*?\"<>|".contains(*c) && !c.is_control()).collect();
+
+    match name.trim() {
+        "" => String::from(DEFAULT_NAME),
+        val => val.to_string()
+    }
+}
+
+fn get_paths(titles: &[String], options: &FileOptions, extension: &str) -> Vec<String> {
+    let names: Vec<String> = if options.per_puzzle {
+        titles.iter().enumerate().map(|(i, title)| fill_template(&options.template, title, i + 1, &options.time)).collect()
+    }
+    else {
+        vec![fill_template(&options.template, titles.first().map(|title| title.as_str()).unwrap_or(""), 1, &options.time)]
+    };
+
+    //Files of the same run never replace each other
+    let mut paths: Vec<String> = Vec::new();
+    for name in names {
+        let mut path = format!("{}/{}.{}", options.directory, name, extension);
+        let mut number = 2;
+        while paths.contains(&path) || (!options.overwrite && Path::new(&path).exists()) {
+            path = format!("{}/{} ({}).{}", options.directory, name, number, extension);
+            number += 1;
+        }
+        paths.push(path);
+    }
+
+    paths
+}
+
+#[derive(Clone)]
+pub struct FileOptions {
+    pub directory: String,
+    pub template: String, //File name without the extension
+    pub per_puzzle: bool,
+    pub overwrite: bool,
+    pub time: DateTime<Local> //Taken once per export, so checking for existing files and writing them use the same names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{ NaiveDate, TimeZone };
+
+    #[test]
+    fn templates_leave_out_characters_of_paths() {
+        let time = Local.from_local_datetime(&NaiveDate::from_ymd_opt(2024, 1, 31).unwrap().and_hms_opt(9, 5, 0).unwrap()).unwrap();
+        assert_eq!(fill_template("{title}_{n}", "Either/Or: \"Yes?\"", 2, &time), "EitherOr Yes_2");
+        assert_eq!(fill_template("  {title} ", "", 1, &time), DEFAULT_NAME);
+        assert_eq!(fill_template("{date} {time}", "", 1, &time), "2024-01-31 09-05-00");
+    }
+
+    #[test]
+    fn taken_names_get_a_number() {
+        let directory = std::env::temp_dir().join(format!("wordsearch_paths_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("Fruits.pdf"), "").unwrap();
+        let options = FileOptions {
+            directory: directory.to_string_lossy().to_string(),
+            template: String::from("{title}"),
+            per_puzzle: true,
+            overwrite: false,
+            time: Local::now()
+        };
+        let titles = vec![String::from("Animals"), String::from("Animals"), String::from("Fruits")];
+
+        let paths = get_paths(&titles, &options, "pdf");
+        let conflicts = get_conflicts(&titles, &options, "pdf");
+        let single = get_paths(&titles, &FileOptions { per_puzzle: false, ..options.clone() }, "pdf");
+        let _ = fs::remove_dir_all(&directory);
+
+        let directory = directory.to_string_lossy();
+        assert_eq!(paths, vec![
+            format!("{}/Animals.pdf", directory),
+            format!("{}/Animals (2).pdf", directory),
+            format!("{}/Fruits (2).pdf", directory)
+        ]);
+        assert_eq!(conflicts, vec![format!("{}/Fruits.pdf", directory)]);
+        assert_eq!(single, vec![format!("{}/Animals.pdf", directory)]);
+    }
+}
@@ -1,37 +1,86 @@
-use std::io::Error;
+use std::io::{ Error, ErrorKind };
 use std::sync::Mutex;
 use std::thread;
-use pdf_canvas::{ Pdf, BuiltinFont, FontSource, Canvas };
-use pdf_canvas::graphicsstate::Color;
+use std::time::Instant;
+use pdf_canvas::{ Pdf, Canvas };
+use pdf_canvas::graphicsstate::{ Color, Matrix, CapStyle };
+use chrono::Local;
+use serde::{ Serialize, Deserialize };
 
 use crate::request::TitledWordSearch;
+use crate::word_search::{ Placement, PuzzleKind, MESSAGE_NOTE };
+use crate::fonts::Font;
+use crate::img::{ self, Thumbnail };
 
-static FONT: BuiltinFont = BuiltinFont::Times_Roman;
+static THUMBNAIL_PIXELS: usize = 40;
+static MIN_SCALE: f32 = 0.2; //The word list isn't shrunk further, pages that still don't fit are an error
 
 lazy_static! {
     static ref COUNT: Mutex<u32> = Mutex::new(0);
     static ref MAX_COUNT: Mutex<u32> = Mutex::new(0); 
 }
 
-pub fn create_pdf(word_search_list: Vec<TitledWordSearch>, width: f32, height: f32, directory: &str) -> Result<(), Error> {
-    let mut pdf = Pdf::create(&format!("{}/wordsearch.pdf", directory))?;
+pub fn create_pdf(word_search_list: Vec<TitledWordSearch>, options: &PdfOptions, path: &str) -> Result<(), Error> {
+    let start = Instant::now();
+    let result = write_pdf(word_search_list, options, path);
+    set_count(0);
+    set_max_count(1);
+
+    match &result {
+        Ok(_) => log::info!("Wrote \"{}\" in {} ms", path, start.elapsed().as_millis()),
+        Err(err) => {
+            log::error!("\"{}\" could not be written: {}", path, err);
+            let _ = std::fs::remove_file(path); //Don't leave a broken file behind
+        }
+    }
+    result
+}
+
+fn write_pdf(word_search_list: Vec<TitledWordSearch>, options: &PdfOptions, path: &str) -> Result<(), Error> {
+    let mut pdf = Pdf::create(path)?;
 
-    set_max_count(word_search_list.len() as u32);
+    //Every page is a word search and whether it is the answer key for it, the answer keys come after all word searches
+    let mut pages: Vec<(usize, bool)> = (0..word_search_list.len()).map(|index| (index, false)).collect();
+    if options.answer_key {
+        for index in 0..word_search_list.len() {
+            pages.push((index, true));
+        }
+    }
+
+    set_max_count(pages.len() as u32);
     set_count(0);
 
-    for word_search in word_search_list {
-        set_count(get_count() + 1);
-        pdf.render_page(width, height, |mut canvas| {
-            draw_page(&word_search, &mut canvas, width, height)?;
+    let date = Local::now().format("%Y-%m-%d").to_string();
+
+    let (sheet_width, sheet_height, cells) = get_cells(options.layout, options.page.width, options.page.height);
+    for side in get_sides(options.layout, pages.len(), cells.len()) {
+        pdf.render_page(sheet_width, sheet_height, |canvas| {
+            for i in 0..side.len() {
+                let page = match side[i] {
+                    Some(val) => val,
+                    None => continue //Empty cell or blank booklet page
+                };
+                let (index, answers) = pages[page];
+                let (x, y, cell_width, cell_height) = cells[i];
+
+                set_count(get_count() + 1);
+                canvas.gsave()?;
+                canvas.concat(Matrix::translate(x, y))?;
+                let info = PageInfo {
+                    answers,
+                    number: page + 1,
+                    count: pages.len(),
+                    date: &date
+                };
+                draw_page(&word_search_list[index], canvas, cell_width, cell_height, options, &info)?;
+                canvas.grestore()?;
+            }
             Ok(())
         })?;
     }
 
     pdf.finish()?;
 
-    set_count(0);
-    set_max_count(1);
-
     Ok(())
 }
 
@@ -39,84 +88,777 @@ pub fn get_progress() -> f32 {
     (get_count() as f32) / (get_max_count() as f32)
 }
 
-fn draw_page(word_search: &TitledWordSearch, canvas: &mut Canvas, width: f32, height: f32) -> Result<(), Error> {
-    let mut title: Vec<char> = word_search.title.chars().collect();
-    title[0] = title[0].to_string().to_uppercase().chars().next().unwrap();
-    let title: String = title.into_iter().collect();
-    canvas.center_text(width / 2.0, height - height / 20.0, FONT, get_font_size(width) * 1.5, &title)?;
+pub fn get_format(format: &str) -> (f32, f32) {
+    match format {
+        "Letter" => (612.0, 792.0),
+        "Half Letter" => (396.0, 612.0),
+        "DINA4" => (595.0, 842.0),
+        _ => (420.0, 595.0) //DINA5
+    }
+}
+
+//Returns the sheet size and the area of every page placed on a sheet
+fn get_cells(layout: Layout, width: f32, height: f32) -> (f32, f32, Vec<Cell>) {
+    match layout {
+        Layout::Single => (width, height, vec![(0.0, 0.0, width, height)]),
+        Layout::TwoUp | Layout::Booklet => { //Two half size pages on a sheet turned sideways, so they keep the page orientation
+            if height >= width {
+                (height, width, vec![
+                    (0.0, 0.0, height / 2.0, width),
+                    (height / 2.0, 0.0, height / 2.0, width)
+                ])
+            }
+            else {
+                (height, width, vec![
+                    (0.0, width / 2.0, height, width / 2.0),
+                    (0.0, 0.0, height, width / 2.0)
+                ])
+            }
+        },
+        Layout::FourUp => (
+            width,
+            height,
+            vec![
+                (0.0, height / 2.0, width / 2.0, height / 2.0),
+                (width / 2.0, height / 2.0, width / 2.0, height / 2.0),
+                (0.0, 0.0, width / 2.0, height / 2.0),
+                (width / 2.0, 0.0, width / 2.0, height / 2.0)
+            ]
+        )
+    }
+}
+
+//Returns the word search shown in every cell of every printed side
+fn get_sides(layout: Layout, count: usize, cells: usize) -> Vec<Vec<Option<usize>>> {
+    let get_index = |index: usize| if index < count { Some(index) } else { None };
+    let mut sides: Vec<Vec<Option<usize>>> = Vec::new();
+
+    if let Layout::Booklet = layout { //Saddle stitch order, so the folded sheets read front to back
+        let mut pages = count;
+        if !pages.is_multiple_of(4) {
+            pages += 4 - pages % 4;
+        }
+
+        for sheet in 0..(pages / 4) {
+            sides.push(vec![get_index(pages - 1 - sheet * 2), get_index(sheet * 2)]); //Front
+            sides.push(vec![get_index(sheet * 2 + 1), get_index(pages - 2 - sheet * 2)]); //Back
+        }
+    }
+    else {
+        let mut index = 0;
+        while index < count {
+            let mut side = Vec::new();
+            for i in 0..cells {
+                side.push(get_index(index + i));
+            }
+            sides.push(side);
+            index += cells;
+        }
+    }
+
+    sides
+}
+
+fn draw_page(word_search: &TitledWordSearch, canvas: &mut Canvas, width: f32, height: f32, options: &PdfOptions, info: &PageInfo) -> Result<(), Error> {
+    let answers = info.answers;
+    let mut chars = word_search.title.chars();
+    let mut title: String = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new()
+    };
+    if answers {
+        title = if title.is_empty() { String::from("Answers") } else { format!("{} - Answers", title) };
+    }
+
+    //The header and footer are placed inside the margins, the rest of the page is left for the word search
+    let mut margins = options.page.margins;
+    let template_size = get_font_size(width - margins.left - margins.right) * 0.5;
+    if !options.header.is_empty() {
+        let header = fill_template(&options.header, &title, &options.class_name, info);
+        draw_template(canvas, &options.list_font, &header, height - margins.top - template_size, width, &margins, template_size)?;
+        margins.top += template_size * 2.5;
+    }
+    if !options.footer.is_empty() {
+        let footer = fill_template(&options.footer, &title, &options.class_name, info);
+        draw_template(canvas, &options.list_font, &footer, margins.bottom, width, &margins, template_size)?;
+        margins.bottom += template_size * 2.5;
+    }
+
+    let layout = get_page_layout(word_search, &title, width, height, &margins, options, answers)?;
+    for i in 0..layout.title.len() {
+        options.title_font.center_text(canvas, layout.left + layout.content_width / 2.0, layout.title_y - (i as f32) * layout.title_size * 1.2, layout.title_size, &layout.title[i])?;
+    }
+    for i in 0..layout.subtitle.len() {
+        options.list_font.center_text(canvas, layout.left + layout.content_width / 2.0, layout.subtitle_y - (i as f32) * layout.subtitle_size * 1.4, layout.subtitle_size, &layout.subtitle[i])?;
+    }
+    if word_search.word_search.kind == PuzzleKind::WordSearch {
+        if answers {
+            draw_answers(canvas, &word_search.word_search.placements, &layout)?;
+        }
+        draw_field(canvas, &word_search.word_search.field, &layout, &options.grid_font)?;
+    }
+    else {
+        draw_blank_field(canvas, word_search, &layout, &options.grid_font, answers)?;
+    }
+    draw_word_list(canvas, &layout, &options.list_font, layout.checkboxes)?;
+    for i in 0..layout.note.len() {
+        options.list_font.left_text(canvas, layout.left, layout.note_top - layout.list_size - (i as f32) * layout.line_height, layout.list_size, &layout.note[i])?;
+    }
+
+    Ok(())
+}
+
+//Replaces the placeholders {title}, {class}, {date}, {page} and {pages}
+fn fill_template(template: &str, title: &str, class_name: &str, info: &PageInfo) -> String {
+    template.replace("{title}", title)
+        .replace("{class}", class_name)
+        .replace("{date}", info.date)
+        .replace("{pages}", &info.count.to_string())
+        .replace("{page}", &info.number.to_string())
+}
+
+//Templates are split into a left, center and right part by "|"
+fn draw_template(canvas: &mut Canvas, font: &Font, text: &str, y: f32, width: f32, margins: &Margins, size: f32) -> Result<(), Error> {
+    let parts: Vec<&str> = text.splitn(3, '|').map(|part| part.trim()).collect();
+    let left = margins.left;
+    let right = width - margins.right;
+
+    font.left_text(canvas, left, y, size, parts[0])?;
+    if parts.len() > 1 {
+        font.center_text(canvas, (left + right) / 2.0, y, size, parts[1])?;
+    }
+    if parts.len() > 2 {
+        font.right_text(canvas, right, y, size, parts[2])?;
+    }
+
+    Ok(())
+}
 
+//Fits the title, grid and word list inside the margins, shrinking the grid and the word list if they would overflow
+fn get_page_layout(word_search: &TitledWordSearch, title: &str, width: f32, height: f32, margins: &Margins, options: &PdfOptions, answers: bool) -> Result<PageLayout, Error> {
     let w = word_search.word_search.field.len() as f32;
     let h = word_search.word_search.field[0].len() as f32;
-    let space = (width - width / 4.0) / w;
-    draw_field(canvas, &word_search.word_search.field, w, h, space, width, height)?;
+    let entries = get_list_entries(word_search, &options.word_list);
+    let checkboxes = options.word_list.checkboxes && word_search.word_search.kind == PuzzleKind::WordSearch;
+    let note = match &word_search.word_search.message {
+        Some(message) if answers => format!("Hidden message: {}", message),
+        Some(_) => String::from(MESSAGE_NOTE),
+        None => String::new()
+    };
+
+    let content_width = width - margins.left - margins.right;
+    let content_height = height - margins.top - margins.bottom;
+    let top = height - margins.top;
+    let font_size = get_font_size(content_width.min(content_height * 0.75));
+
+    //Long titles and subtitles wrap, only words that don't fit on a line by themselves make the title smaller
+    let mut title_size = font_size * 1.5;
+    for word in title.split_whitespace() {
+        let word_width = options.title_font.get_width(title_size, word);
+        if word_width > content_width {
+            title_size *= content_width / word_width;
+        }
+    }
+    let title_lines = wrap_text(title, &options.title_font, title_size, content_width);
+    let subtitle_size = font_size * 0.75;
+    let subtitle_lines = if word_search.subtitle.trim() == "" { Vec::new() } else { wrap_text(&word_search.subtitle, &options.list_font, subtitle_size, content_width) };
+    let subtitle_top = top - title_size * (1.5 + 1.2 * (title_lines.len() as f32 - 1.0));
+    let mut title_height = top - subtitle_top;
+    if !subtitle_lines.is_empty() {
+        title_height += subtitle_size * (0.5 + 1.4 * subtitle_lines.len() as f32);
+    }
+
+    let mut scale = 1.0;
+    loop {
+        let list_size = font_size * 0.75 * scale;
+        let list = get_list_layout(&entries, &options.list_font, list_size, content_width, checkboxes);
+        let note_lines = if note.is_empty() { Vec::new() } else { wrap_text(&note, &options.list_font, list_size, content_width) };
+        let line_height = list_size * 1.5;
+        let gap = list_size;
+        let mut list_height = list.height;
+        if !note_lines.is_empty() {
+            list_height += (0.5 + note_lines.len() as f32) * line_height;
+        }
 
-    let word_list_start = get_start_search(height) - h * space - height / 32.0;
-    draw_word_list(canvas, &word_search.word_search.word_list, word_list_start, width, height)?;
+        //The border around the grid adds an eighth of a cell on every side
+        let grid_height = (content_height - title_height - gap - list_height).max(0.0);
+        let space = (content_width / (w + 0.25)).min(grid_height / (h + 0.25));
+        let fits = list.fits && space > 0.0 && title_height + gap + list_height <= content_height;
+
+        if fits && (space >= list_size * 1.5 || scale < MIN_SCALE) {
+            let grid_top = top - title_height - space / 8.0;
+            let char_size = (space * 0.6).min(font_size);
+            let list_top = grid_top - h * space - space / 8.0 - gap;
+            return Ok(PageLayout {
+                left: margins.left,
+                content_width,
+                title: title_lines,
+                title_y: top - title_size * 0.8,
+                title_size,
+                subtitle: subtitle_lines,
+                subtitle_y: subtitle_top - subtitle_size,
+                subtitle_size,
+                grid_left: margins.left + (content_width - w * space) / 2.0,
+                grid_top,
+                space,
+                char_size,
+                char_offset: options.grid_font.get_cap_height(char_size) / 2.0,
+                list_top,
+                list_left: margins.left + (content_width - list.width) / 2.0,
+                list_size,
+                note_top: list_top - list.height - line_height * 0.5,
+                note: note_lines,
+                list,
+                line_height,
+                checkboxes
+            });
+        }
+        if scale < MIN_SCALE {
+            let name = if title.is_empty() { String::from("A word search") } else { format!("\"{}\"", title) };
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{} doesn't fit on the page, use larger pages, smaller margins, shorter instructions or fewer words", name)));
+        }
+
+        scale *= 0.9;
+    }
+}
+
+//Marks every word with a gray bar through its letters
+fn draw_answers(canvas: &mut Canvas, placements: &Vec<Placement>, layout: &PageLayout) -> Result<(), Error> {
+    let space = layout.space;
+
+    canvas.gsave()?;
+    canvas.set_stroke_color(Color::gray(210))?;
+    canvas.set_line_width(space * 0.7)?;
+    canvas.set_line_cap_style(CapStyle::Round)?;
+    for placement in placements {
+        let cells = placement.cells();
+        let (start_x, start_y) = cells[0];
+        let (end_x, end_y) = cells[cells.len() - 1];
+        canvas.line(
+            layout.grid_left + (start_x as f32 + 0.5) * space, layout.grid_top - (start_y as f32 + 0.5) * space,
+            layout.grid_left + (end_x as f32 + 0.5) * space, layout.grid_top - (end_y as f32 + 0.5) * space
+        )?;
+        canvas.stroke()?;
+    }
+    canvas.grestore()?;
 
     Ok(())
 }
 
-fn draw_field(canvas: &mut Canvas, word_search: &Vec<Vec<char>>, w: f32, h: f32, space: f32, width: f32, height: f32) -> Result<(), Error> {
-    for x in 0..(w as usize) {
-        for y in 0..(h as usize) {
-            let x_pos = (x as f32) * space + width / 8.0 + space / 2.0;
-            let y_pos = get_start_search(height) - (y as f32) * space;
-            print_char(canvas, x_pos, y_pos, word_search[x][y], width)?;
+fn draw_field(canvas: &mut Canvas, word_search: &[Vec<char>], layout: &PageLayout, font: &Font) -> Result<(), Error> {
+    let w = word_search.len() as f32;
+    let h = word_search[0].len() as f32;
+    let space = layout.space;
+
+    for (x, column) in word_search.iter().enumerate() {
+        for (y, letter) in column.iter().enumerate() {
+            let x_pos = layout.grid_left + (x as f32 + 0.5) * space;
+            let y_pos = layout.grid_top - (y as f32 + 0.5) * space - layout.char_offset; //Center the capital letters in the cell
+            print_char(canvas, font, x_pos, y_pos, *letter, layout.char_size)?;
         }
     }
 
     canvas.set_stroke_color(Color::rgb(0, 0, 0))?;
-    canvas.rectangle(width / 8.0 - space / 8.0, get_start_search(height) - space * h + space / 2.0, space * w + space / 4.0, space * h + space / 4.0)?;
+    canvas.rectangle(layout.grid_left - space / 8.0, layout.grid_top - space * h - space / 8.0, space * w + space / 4.0, space * h + space / 4.0)?;
     canvas.stroke()?;
 
     Ok(())
 }
 
-fn draw_word_list(canvas: &mut Canvas, word_list: &Vec<String>, start_y: f32, width: f32, height: f32) -> Result<(), Error> {
-    let mut max_width: f32 = 0.0;
-    for word in word_list {
-        let width = FONT.get_width(get_font_size(width) * 0.75, &word);
-        if width > max_width {
-            max_width = width;
+//Fill-ins block the unused cells, criss-crosses leave them out and number the first cell of every word
+fn draw_blank_field(canvas: &mut Canvas, word_search: &TitledWordSearch, layout: &PageLayout, font: &Font, answers: bool) -> Result<(), Error> {
+    let field = &word_search.word_search.field;
+    let space = layout.space;
+    let number_size = space * 0.3;
+
+    canvas.set_stroke_color(Color::rgb(0, 0, 0))?;
+    canvas.set_fill_color(Color::rgb(0, 0, 0))?;
+    for (x, column) in field.iter().enumerate() {
+        for (y, letter) in column.iter().enumerate() {
+            let left = layout.grid_left + (x as f32) * space;
+            let bottom = layout.grid_top - (y as f32 + 1.0) * space;
+            if *letter != (0 as char) {
+                canvas.rectangle(left, bottom, space, space)?;
+                canvas.stroke()?;
+                if answers {
+                    print_char(canvas, font, left + space / 2.0, bottom + space / 2.0 - layout.char_offset, *letter, layout.char_size)?;
+                }
+            }
+            else if word_search.word_search.kind == PuzzleKind::FillIn {
+                canvas.rectangle(left, bottom, space, space)?;
+                canvas.fill()?;
+            }
+        }
+    }
+
+    if word_search.word_search.kind == PuzzleKind::CrissCross {
+        let numbers = word_search.word_search.get_numbers();
+        for (placement, number) in word_search.word_search.placements.iter().zip(numbers.iter()) {
+            let left = layout.grid_left + (placement.x as f32) * space;
+            let top = layout.grid_top - (placement.y as f32) * space;
+            font.left_text(canvas, left + space * 0.08, top - space * 0.08 - font.get_cap_height(number_size), number_size, &number.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+//Returns the text printed for every word, either the word itself or its clue followed by the word length,
+//together with the picture shown instead of the text if there is one
+fn get_list_entries(word_search: &TitledWordSearch, options: &WordListOptions) -> Vec<(String, Option<Thumbnail>)> {
+    match word_search.word_search.kind {
+        PuzzleKind::FillIn => return get_length_entries(&word_search.word_search.word_list),
+        PuzzleKind::CrissCross => return get_clue_entries(word_search),
+        PuzzleKind::WordSearch => ()
+    }
+
+    let mut entries: Vec<(String, usize, Option<Thumbnail>)> = Vec::new();
+    for word in &word_search.word_search.word_list {
+        let length = word.chars().filter(|c| !c.is_whitespace()).count();
+        let picture = match word_search.images.get(word) {
+            Some(path) if options.pictures => img::load_thumbnail(path, THUMBNAIL_PIXELS),
+            _ => None
+        };
+        if !options.clues {
+            entries.push((word.clone(), length, picture));
+            continue;
+        }
+
+        entries.push((format!("{} ({})", word_search.get_clue(word), length), length, picture));
+    }
+
+    match options.order {
+        WordOrder::Insertion => (),
+        WordOrder::Alphabetical => entries.sort_by(|a, b| a.0.cmp(&b.0)),
+        WordOrder::Length => entries.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)))
+    }
+
+    entries.into_iter().map(|entry| (entry.0, entry.2)).collect()
+}
+
+//Fill-ins list the words grouped by their length, e.g. "4 Letters: CAKE, TREE"
+fn get_length_entries(word_list: &[String]) -> Vec<(String, Option<Thumbnail>)> {
+    let mut words = word_list.to_vec();
+    words.sort_by(|a, b| a.chars().count().cmp(&b.chars().count()).then(a.cmp(b)));
+
+    let mut groups: Vec<(usize, Vec<String>)> = Vec::new();
+    for word in words {
+        let length = word.chars().count();
+        match groups.last_mut() {
+            Some(group) if group.0 == length => group.1.push(word),
+            _ => groups.push((length, vec![word]))
+        }
+    }
+
+    groups.into_iter().map(|group| (format!("{} Letters: {}", group.0, group.1.join(", ")), None)).collect()
+}
+
+//Criss-crosses list numbered clues under "Across" and "Down" headings
+fn get_clue_entries(word_search: &TitledWordSearch) -> Vec<(String, Option<Thumbnail>)> {
+    let numbers = word_search.word_search.get_numbers();
+    let mut entries: Vec<(String, Option<Thumbnail>)> = Vec::new();
+    for (orientation, heading) in [(0, "Across"), (1, "Down")].iter() {
+        let mut clues: Vec<(usize, String)> = Vec::new();
+        for (placement, number) in word_search.word_search.placements.iter().zip(numbers.iter()) {
+            if placement.orientation == *orientation {
+                clues.push((*number, format!("{}. {} ({})", number, word_search.get_clue(&placement.word), placement.length)));
+            }
+        }
+        clues.sort();
+
+        if !clues.is_empty() {
+            entries.push((heading.to_string(), None));
+            entries.extend(clues.into_iter().map(|clue| (clue.1, None)));
+        }
+    }
+
+    entries
+}
+
+//Uses as many columns as fit, each only as wide as its longest entry.
+//Entries that don't even fit in a single column are wrapped onto several lines
+fn get_list_layout(entries: &Vec<(String, Option<Thumbnail>)>, font: &Font, size: f32, width: f32, checkboxes: bool) -> ListLayout {
+    if entries.iter().any(|entry| entry.1.is_some()) {
+        return get_picture_layout(entries, font, size, width, checkboxes);
+    }
+
+    let gap = size * 2.0;
+    let box_width = if checkboxes { size * 1.2 } else { 0.0 };
+    let widths: Vec<f32> = entries.iter().map(|entry| font.get_width(size, &entry.0)).collect();
+
+    for cols in (1..=entries.len()).rev() {
+        let mut rows = entries.len() / cols;
+        if !entries.len().is_multiple_of(cols) {
+            rows += 1;
+        }
+
+        let mut columns: Vec<f32> = Vec::new();
+        let mut total = 0.0;
+        for start in (0..entries.len()).step_by(rows) { //Words run down the columns
+            let end = (start + rows).min(entries.len());
+            let col_width = widths[start..end].iter().fold(0.0f32, |max, width| max.max(*width));
+            if !columns.is_empty() {
+                total += gap;
+            }
+            columns.push(total);
+            total += box_width + col_width;
+        }
+
+        if total <= width {
+            let mut list_entries: Vec<ListEntry> = Vec::new();
+            for (i, entry) in entries.iter().enumerate() {
+                list_entries.push(ListEntry {
+                    col: i / rows,
+                    row: i % rows,
+                    lines: vec![entry.0.clone()],
+                    size,
+                    picture: None
+                });
+            }
+
+            return ListLayout {
+                columns,
+                entries: list_entries,
+                row_height: size * 1.5,
+                height: rows as f32 * size * 1.5,
+                picture_size: 0.0,
+                width: total,
+                fits: true
+            };
         }
     }
-    let cols = ((width - width / 4.0) / (max_width * 1.25)) as usize;
-    let space_x = (width - width / 4.0) / (cols as f32);
 
-    let mut rows = word_list.len() / cols;
-    if word_list.len() % cols != 0 {
+    let mut list_entries: Vec<ListEntry> = Vec::new();
+    let mut line = 0;
+    let mut fits = true;
+    for (entry, _) in entries {
+        let lines = wrap_text(entry, font, size, width - box_width);
+        for text in &lines {
+            fits = fits && font.get_width(size, text) <= width - box_width;
+        }
+
+        let count = lines.len();
+        list_entries.push(ListEntry {
+            col: 0,
+            row: line,
+            lines,
+            size,
+            picture: None
+        });
+        line += count;
+    }
+
+    ListLayout {
+        columns: vec![0.0],
+        entries: list_entries,
+        row_height: size * 1.5,
+        height: line as f32 * size * 1.5,
+        picture_size: 0.0,
+        width,
+        fits
+    }
+}
+
+//Pictures are laid out in rows of squares, words without a picture are written into their square instead
+//and shrunk if they don't fit
+fn get_picture_layout(entries: &[(String, Option<Thumbnail>)], font: &Font, size: f32, width: f32, checkboxes: bool) -> ListLayout {
+    let gap = size * 2.0;
+    let box_width = if checkboxes { size * 1.2 } else { 0.0 };
+    let picture_size = size * 4.0;
+    let cell_width = box_width + picture_size;
+
+    let cols = (((width + gap) / (cell_width + gap)) as usize).max(1).min(entries.len());
+    let mut rows = entries.len() / cols;
+    if !entries.len().is_multiple_of(cols) {
         rows += 1;
     }
-    let tot_height = start_y - height / 32.0;
-    let space_y = tot_height / (rows as f32);
 
-    for x in 0..cols {
-        for y in 0..rows {
-            if x + y * cols >= word_list.len() {
-                break;
+    let mut list_entries: Vec<ListEntry> = Vec::new();
+    for (i, (text, picture)) in entries.iter().enumerate() {
+        let mut lines: Vec<String> = Vec::new();
+        let mut text_size = size;
+        if picture.is_none() {
+            lines = wrap_text(text, font, size, picture_size);
+            for line in &lines {
+                text_size = text_size.min(size * picture_size / font.get_width(size, line).max(1.0));
+            }
+            text_size = text_size.min(picture_size / (lines.len() as f32 * 1.5));
+        }
+
+        list_entries.push(ListEntry {
+            col: i % cols,
+            row: i / cols,
+            lines,
+            size: text_size,
+            picture: picture.clone()
+        });
+    }
+
+    ListLayout {
+        columns: (0..cols).map(|col| col as f32 * (cell_width + gap)).collect(),
+        entries: list_entries,
+        row_height: picture_size + size,
+        height: rows as f32 * (picture_size + size) - size,
+        picture_size,
+        width: cols as f32 * cell_width + (cols - 1) as f32 * gap,
+        fits: cell_width <= width
+    }
+}
+
+fn wrap_text(text: &str, font: &Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let extended = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+        if !line.is_empty() && font.get_width(size, &extended) > width {
+            lines.push(line);
+            line = word.to_string();
+        }
+        else {
+            line = extended;
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn draw_word_list(canvas: &mut Canvas, layout: &PageLayout, font: &Font, checkboxes: bool) -> Result<(), Error> {
+    let size = layout.list_size;
+    let box_size = size * 0.7;
+
+    for entry in &layout.list.entries {
+        let x_pos = layout.list_left + layout.list.columns[entry.col];
+        let top = layout.list_top - (entry.row as f32) * layout.list.row_height;
+        let y_pos = top - size;
+
+        let mut text_x = x_pos;
+        if checkboxes {
+            canvas.set_stroke_color(Color::rgb(0, 0, 0))?;
+            canvas.rectangle(x_pos, y_pos, box_size, box_size)?;
+            canvas.stroke()?;
+            text_x += size * 1.2;
+        }
+
+        let picture_size = layout.list.picture_size;
+        if let Some(picture) = &entry.picture {
+            draw_picture(canvas, picture, text_x, top - picture_size, picture_size)?;
+        }
+        else if picture_size > 0.0 { //Centered in the square of the missing picture
+            let line_height = entry.size * 1.5;
+            let first_y = top - picture_size / 2.0 + (entry.lines.len() as f32 - 1.0) * line_height / 2.0 - font.get_cap_height(entry.size) / 2.0;
+            for i in 0..entry.lines.len() {
+                font.center_text(canvas, text_x + picture_size / 2.0, first_y - (i as f32) * line_height, entry.size, &entry.lines[i])?;
+            }
+        }
+        else {
+            for i in 0..entry.lines.len() {
+                font.left_text(canvas, text_x, y_pos - (i as f32) * layout.line_height, size, &entry.lines[i])?;
             }
+        }
+    }
 
-            let x_pos = height / 8.0 + (x as f32) * space_x;
-            let y_pos = start_y - (y as f32) * space_y;
+    Ok(())
+}
 
-            canvas.left_text(x_pos, y_pos, FONT, get_font_size(width) * 0.75, &word_list[x + y * cols])?;
+//Pdf_canvas can't embed images, so every row of the thumbnail is drawn as rectangles of the same color
+fn draw_picture(canvas: &mut Canvas, picture: &Thumbnail, x: f32, y: f32, size: f32) -> Result<(), Error> {
+    let pixel = size / (picture.width.max(picture.height) as f32);
+    let left = x + (size - picture.width as f32 * pixel) / 2.0;
+    let top = y + size - (size - picture.height as f32 * pixel) / 2.0;
+
+    canvas.gsave()?;
+    for row in 0..picture.height {
+        let mut start = 0;
+        while start < picture.width {
+            let color = picture.pixels[row * picture.width + start];
+            let mut end = start + 1;
+            while end < picture.width && picture.pixels[row * picture.width + end] == color {
+                end += 1;
+            }
+
+            if color != [255, 255, 255] { //The paper is already white
+                canvas.set_fill_color(Color::rgb(color[0], color[1], color[2]))?;
+                //Slightly larger than the pixel so no gaps show between the rectangles
+                canvas.rectangle(left + start as f32 * pixel, top - (row + 1) as f32 * pixel, (end - start) as f32 * pixel + pixel * 0.05, pixel * 1.05)?;
+                canvas.fill()?;
+            }
+            start = end;
         }
     }
+    canvas.grestore()?;
 
     Ok(())
 }
 
-fn print_char(canvas: &mut Canvas, x: f32, y: f32, c: char, width: f32) -> Result<(), Error> {
-    canvas.center_text(x, y, FONT, get_font_size(width), &c.to_string())?;
+fn print_char(canvas: &mut Canvas, font: &Font, x: f32, y: f32, c: char, size: f32) -> Result<(), Error> {
+    font.center_text(canvas, x, y, size, &c.to_string())?;
     Ok(())
 }
 
 fn get_font_size(width: f32) -> f32 {
-    width / 30.0
+    width / 22.5
+}
+
+pub fn mm_to_points(mm: f32) -> f32 {
+    mm * 72.0 / 25.4
 }
 
-fn get_start_search(height: f32) -> f32 {
-    height - height / 7.0
+pub fn in_to_points(inches: f32) -> f32 {
+    inches * 72.0
+}
+
+pub struct PdfOptions {
+    pub page: PageSetup,
+    pub layout: Layout,
+    pub header: String,
+    pub footer: String,
+    pub class_name: String,
+    pub title_font: Font,
+    pub grid_font: Font,
+    pub list_font: Font,
+    pub word_list: WordListOptions,
+    pub answer_key: bool //Adds a page with the solution for every word search
+}
+
+pub struct WordListOptions {
+    pub order: WordOrder,
+    pub checkboxes: bool,
+    pub clues: bool, //Print clues instead of the words
+    pub pictures: bool //Show the pictures of custom word lists instead of the words
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PageSetup { //All sizes are in points
+    pub width: f32,
+    pub height: f32,
+    pub margins: Margins
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Margins {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32
+}
+
+type Cell = (f32, f32, f32, f32); //x, y, width and height of a page on a sheet
+
+//Where a page is in the document, for the header and footer
+struct PageInfo<'a> {
+    answers: bool, //The page is the answer key of its word search
+    number: usize,
+    count: usize,
+    date: &'a str
+}
+
+struct PageLayout {
+    left: f32,
+    content_width: f32,
+    title: Vec<String>,
+    title_y: f32,
+    title_size: f32,
+    subtitle: Vec<String>, //Instructions below the title
+    subtitle_y: f32,
+    subtitle_size: f32,
+    grid_left: f32,
+    grid_top: f32,
+    space: f32,
+    char_size: f32,
+    char_offset: f32,
+    list_top: f32,
+    list_left: f32,
+    list_size: f32,
+    list: ListLayout,
+    note_top: f32,
+    note: Vec<String>, //Tells about or shows the hidden message
+    line_height: f32,
+    checkboxes: bool //Only word searches have words to check off
+}
+
+struct ListLayout {
+    columns: Vec<f32>, //Offsets of the columns from the left of the word list
+    entries: Vec<ListEntry>,
+    row_height: f32,
+    height: f32,
+    picture_size: f32,
+    width: f32,
+    fits: bool
+}
+
+struct ListEntry {
+    col: usize,
+    row: usize,
+    lines: Vec<String>,
+    size: f32,
+    picture: Option<Thumbnail>
+}
+
+impl PageSetup {
+    pub fn new(width: f32, height: f32, landscape: bool, margins: Margins) -> PageSetup {
+        let (width, height) = if landscape == (width < height) { (height, width) } else { (width, height) };
+
+        PageSetup {
+            width,
+            height,
+            margins
+        }
+    }
+}
+
+impl Margins {
+    pub fn uniform(margin: f32) -> Margins {
+        Margins {
+            top: margin,
+            right: margin,
+            bottom: margin,
+            left: margin
+        }
+    }
+
+    //Parses either one margin for all sides or four margins (top, right, bottom, left) in millimeters
+    pub fn parse(text: &str) -> Option<Margins> {
+        let mut values: Vec<f32> = Vec::new();
+        for part in text.split([',', ' ']).filter(|part| !part.is_empty()) {
+            match part.parse::<f32>() {
+                Ok(val) if val >= 0.0 => values.push(mm_to_points(val)),
+                _ => return None
+            }
+        }
+
+        match values.len() {
+            1 => Some(Margins::uniform(values[0])),
+            4 => Some(Margins {
+                top: values[0],
+                right: values[1],
+                bottom: values[2],
+                left: values[3]
+            }),
+            _ => None
+        }
+    }
+
+    pub fn fit(&self, width: f32, height: f32) -> bool { //At least half of the page has to be left for content
+        self.left + self.right <= width / 2.0 && self.top + self.bottom <= height / 2.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Layout {
+    Single,
+    TwoUp,
+    FourUp,
+    Booklet
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WordOrder {
+    Insertion,
+    Alphabetical,
+    Length
 }
 
 fn set_count(count: u32) {
@@ -157,4 +899,54 @@ fn get_max_count() -> u32 {
             get_max_count()
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn booklet_sheets_fold_in_reading_order() {
+        assert_eq!(get_sides(Layout::Booklet, 8, 2), vec![
+            vec![Some(7), Some(0)],
+            vec![Some(1), Some(6)],
+            vec![Some(5), Some(2)],
+            vec![Some(3), Some(4)]
+        ]);
+
+        //Missing pages up to a multiple of four are left blank
+        assert_eq!(get_sides(Layout::Booklet, 3, 2), vec![vec![None, Some(0)], vec![Some(1), Some(2)]]);
+    }
+
+    #[test]
+    fn last_side_is_filled_with_empty_cells() {
+        assert_eq!(get_sides(Layout::FourUp, 5, 4), vec![vec![Some(0), Some(1), Some(2), Some(3)], vec![Some(4), None, None, None]]);
+        assert_eq!(get_sides(Layout::Single, 2, 1), vec![vec![Some(0)], vec![Some(1)]]);
+    }
+
+    #[test]
+    fn margins_are_one_or_four_millimeter_values() {
+        let margins = Margins::parse("25.4").unwrap();
+        assert_eq!((margins.top, margins.right, margins.bottom, margins.left), (72.0, 72.0, 72.0, 72.0));
+
+        let margins = Margins::parse("0, 25.4 50.8,0").unwrap();
+        assert_eq!((margins.top, margins.right, margins.bottom, margins.left), (0.0, 72.0, 144.0, 0.0));
+
+        for text in ["", "10, 10", "-5", "ten"].iter() {
+            assert!(Margins::parse(text).is_none(), "\"{}\" was accepted", text);
+        }
+    }
+
+    #[test]
+    fn margins_leave_half_the_page() {
+        assert!(Margins::uniform(150.0).fit(612.0, 792.0));
+        assert!(!Margins::uniform(160.0).fit(612.0, 792.0));
+    }
+
+    #[test]
+    fn landscape_swaps_the_page_sides() {
+        let page = PageSetup::new(612.0, 792.0, true, Margins::uniform(0.0));
+        assert_eq!((page.width, page.height), (792.0, 612.0));
+        let page = PageSetup::new(792.0, 612.0, false, Margins::uniform(0.0));
+        assert_eq!((page.width, page.height), (612.0, 792.0));
+    }
+}
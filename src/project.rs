@@ -0,0 +1,49 @@
+use std::fs::{ self, OpenOptions };
+use std::io::Write;
+use serde::{ Serialize, Deserialize };
+
+use crate::word_list::SearchType;
+use crate::word_search::Directions;
+
+//Projects keep the list of word searches so a set of puzzles can be made again later
+pub fn load_project(path: &str) -> Option<Project> {
+    let data = match fs::read_to_string(path) {
+        Ok(val) => val,
+        Err(_) => return None
+    };
+
+    serde_json::from_str(&data).ok()
+}
+
+pub fn save_project(path: &str, project: &Project) -> bool {
+    let data = match serde_json::to_string_pretty(project) {
+        Ok(val) => val,
+        Err(_) => return false
+    };
+
+    let mut file = match OpenOptions::new().write(true).create(true).truncate(true).open(path) {
+        Ok(val) => val,
+        Err(_) => return false
+    };
+
+    file.write_all(data.as_bytes()).is_ok()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub word_searches: Vec<ProjectEntry>
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectEntry {
+    pub base_word: String,
+    pub search_type: SearchType,
+    pub title: String, //Empty strings and missing counts use the general settings
+    pub subtitle: String,
+    pub message: String,
+    pub letter_count: Option<u8>,
+    pub word_count: Option<u8>,
+    pub directions: Directions
+}
@@ -1,8 +1,13 @@
 use std::sync::Mutex;
 use std::thread;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
-use crate::word_list::{ self, SearchType, SearchError };
-use crate::word_search::{ self, WordSearch };
+use crate::word_list::{ self, SearchType, SearchError, Word, Language };
+use crate::word_search::{ self, WordSearch, GenerateOptions, PuzzleKind, Directions };
 
 lazy_static! {
     pub static ref TOTAL: Mutex<i32> = {
@@ -14,32 +19,32 @@ lazy_static! {
     };
 }
 
-pub fn handle_requests(requests: Vec<WordSearchRequest>) -> (Vec<TitledWordSearch>, Vec<String>) {
+pub type RequestError = (String, String); //Title of the word search that failed and the error
+
+pub fn handle_requests(requests: Vec<WordSearchRequest>) -> (Vec<TitledWordSearch>, Vec<RequestError>) {
     set_finished(false);
     calc_total(&requests);
-    let mut error_msgs: Vec<String> = Vec::new();
+    let mut error_msgs: Vec<RequestError> = Vec::new();
     let mut word_search_list: Vec<TitledWordSearch> = Vec::new();
-    for request in requests {
-        let word_search = match get_word_search(&request.word, request.stype.clone(), request.max_count, request.width, request.height) {
+    for (i, mut request) in requests.into_iter().enumerate() {
+        request.seed = request.seed.map(|seed| seed.wrapping_add(i as u64)); //Puzzles of the same run don't repeat each other
+        let title = get_title(&request);
+        let start = Instant::now();
+        log::info!(
+            "Generating \"{}\": {:?} \"{}\", {:?}, {}x{} letters, up to {} words, {:?}, {:?}, seed {:?}",
+            title, request.stype, request.word, request.kind, request.width, request.height, request.max_count, request.directions, request.language, request.seed
+        );
+        let (word_search, words) = match get_word_search(&request) {
             Ok(val) => val,
             Err(err) => {
-                error_msgs.push(format!("{}", err));
+                log::warn!("\"{}\" failed after {} ms: {}", title, start.elapsed().as_millis(), err);
+                error_msgs.push((title, format!("{}", err)));
                 continue;
             }
         };
-        let title = match request.stype {
-            SearchType::RelatedTo => format!("{}", request.word),
-            SearchType::RhymesWith => format!("Rhymes with {}", request.word),
-            SearchType::EndsWith => format!("Ends with -{}", request.word),
-            SearchType::SoundsLike => format!("Sounds like {}", request.word),
-            SearchType::BlankWord => format!("____ {}", request.word),
-            SearchType::WordBlank => format!("{} ____", request.word)
-        };
+        log::info!("Generated \"{}\" with {} words in {} ms", title, word_search.word_list.len(), start.elapsed().as_millis());
 
-        word_search_list.push(TitledWordSearch {
-            title: title,
-            word_search: word_search
-        });
+        word_search_list.push(get_titled_word_search(title, word_search, words, request));
     }
 
     word_search::set_count(0);
@@ -49,6 +54,27 @@ pub fn handle_requests(requests: Vec<WordSearchRequest>) -> (Vec<TitledWordSearc
     (word_search_list, error_msgs)
 }
 
+//Clues from a file replace the definitions found online
+pub fn add_clues(word_search_list: &mut Vec<TitledWordSearch>, clues: &HashMap<String, String>) {
+    for word_search in word_search_list {
+        for word in &word_search.word_search.word_list {
+            if let Some(clue) = clues.get(word) {
+                word_search.clues.insert(word.clone(), clue.clone());
+            }
+        }
+    }
+}
+
+//Places the given words again with the settings of the original request, used by the preview to remove and swap words.
+//The seed only applies to the first generation, otherwise regenerating would give the same puzzle again
+pub fn regenerate(word_search: &TitledWordSearch, word_list: &[String]) -> Result<TitledWordSearch, SearchError> {
+    let mut random = StdRng::from_entropy();
+    let result = place_words(word_list, &word_search.request, &mut random)?;
+    word_search::set_count(0);
+
+    Ok(get_titled_word_search(word_search.title.clone(), result, word_search.words.clone(), word_search.request.clone()))
+}
+
 pub fn get_progress() -> f32 {
     (word_search::get_count() as f32) / (get_total() as f32)
 }
@@ -63,25 +89,77 @@ pub fn get_finished() -> bool {
     }
 }
 
-fn get_word_search(start_word: &str, search_type: SearchType, max_count: usize, width: usize, height: usize) -> Result<WordSearch, SearchError> {
-    let word_list = match word_list::generate(start_word, search_type) {
-        Ok(val) => val,
-        Err(val) => return Err(val)
-    };
+fn get_title(request: &WordSearchRequest) -> String {
+    match &request.title {
+        Some(val) => val.clone(),
+        None => match request.stype {
+            SearchType::RelatedTo => request.word.to_string(),
+            SearchType::RhymesWith => format!("Rhymes with {}", request.word),
+            SearchType::EndsWith => format!("Ends with -{}", request.word),
+            SearchType::SoundsLike => format!("Sounds like {}", request.word),
+            SearchType::BlankWord => format!("____ {}", request.word),
+            SearchType::WordBlank => format!("{} ____", request.word),
+            SearchType::Custom => match Path::new(&request.word).file_stem() {
+                Some(val) => val.to_string_lossy().replace("_", " "),
+                None => request.word.clone()
+            }
+        }
+    }
+}
+
+//The same seed always gives the same puzzle for the same words
+fn get_random(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(val) => StdRng::seed_from_u64(val),
+        None => StdRng::from_entropy()
+    }
+}
 
+fn get_word_search(request: &WordSearchRequest) -> Result<(WordSearch, Vec<Word>), SearchError> {
+    let mut random = get_random(request.seed);
+    let words = word_list::generate(&request.word, request.stype, request.language, &mut random)?;
+    let word_list: Vec<String> = words.iter().map(|word| word.word.clone()).collect();
+
+    match place_words(&word_list, request, &mut random) {
+        Ok(val) => Ok((val, words)),
+        Err(err) => Err(err)
+    }
+}
+
+fn place_words(word_list: &[String], request: &WordSearchRequest, random: &mut StdRng) -> Result<WordSearch, SearchError> {
+    let message = request.message.as_deref();
+    let kind = request.kind;
+    let options = GenerateOptions {
+        max_count: request.max_count,
+        width: request.width,
+        height: request.height,
+        message,
+        kind,
+        directions: request.directions
+    };
     let mut word_search_list: Vec<WordSearch> = Vec::new();
-    for _ in 0..10 {
-        let word_search = match word_search::generate(&word_list, max_count, width, height) {
+    for attempt in 1..=10 {
+        let word_search = match word_search::generate(word_list, &options, random) {
             Some(val) => val,
             None => {
+                log::debug!("Attempt {} for \"{}\" failed", attempt, request.word);
                 continue;
             }
         };
+        log::debug!("Attempt {} for \"{}\" placed {} of {} words", attempt, request.word, word_search.word_list.len(), word_list.len().min(request.max_count));
 
         word_search_list.push(word_search);
     }
 
-    if word_search_list.len() == 0 {
+    if word_search_list.is_empty() && message.is_some() && kind == PuzzleKind::WordSearch {
+        return Err(
+            SearchError::MyError(
+                format!("The hidden message \"{}\" doesn't fit into the word search for \"{}\" (try a different message length, a smaller field or more words)",
+                message.unwrap_or(""), request.word)
+            )
+        );
+    }
+    if word_search_list.is_empty() {
         return Err(
             SearchError::MyError(
                 String::from("The requested word search could not be generated (try increasing the field size)")
@@ -99,6 +177,34 @@ fn get_word_search(start_word: &str, search_type: SearchType, max_count: usize,
     Ok(final_result)
 }
 
+//Clues and pictures are only kept for the words that made it into the word search
+fn get_titled_word_search(title: String, word_search: WordSearch, words: Vec<Word>, request: WordSearchRequest) -> TitledWordSearch {
+    let mut clues: HashMap<String, String> = HashMap::new();
+    let mut images: HashMap<String, String> = HashMap::new();
+    for word in &words {
+        let key = word.word.to_uppercase();
+        if !word_search.word_list.contains(&key) {
+            continue;
+        }
+        if let Some(definition) = &word.definition {
+            clues.insert(key.clone(), definition.clone());
+        }
+        if let Some(image) = &word.image {
+            images.insert(key, image.clone());
+        }
+    }
+
+    TitledWordSearch {
+        title,
+        subtitle: request.subtitle.clone().unwrap_or_default(),
+        word_search,
+        clues,
+        images,
+        words,
+        request
+    }
+}
+
 fn calc_total(requests: &Vec<WordSearchRequest>) {
     let mut tot_words = 0;
     for request in requests {
@@ -138,15 +244,65 @@ fn set_finished(finished: bool) {
     }
 }
 
+#[derive(Clone)]
 pub struct WordSearchRequest {
     pub word: String,
     pub stype: SearchType,
     pub max_count: usize,
     pub height: usize,
-    pub width: usize 
+    pub width: usize,
+    pub message: Option<String>, //Hidden message spelled by the leftover letters
+    pub kind: PuzzleKind,
+    pub directions: Directions,
+    pub title: Option<String>, //Replaces the title made from the base word
+    pub subtitle: Option<String>, //Instructions printed below the title
+    pub language: Language,
+    pub seed: Option<u64> //Makes the puzzle repeatable, None picks a random one
 }
 
 pub struct TitledWordSearch {
     pub title: String,
-    pub word_search: WordSearch
-}
\ No newline at end of file
+    pub subtitle: String,
+    pub word_search: WordSearch,
+    pub clues: HashMap<String, String>, //Keyed by the words in the word list
+    pub images: HashMap<String, String>,
+    pub words: Vec<Word>, //Every fetched word, the preview swaps these in
+    pub request: WordSearchRequest
+}
+
+impl TitledWordSearch {
+    //Words without a clue get their first letter as a hint
+    pub fn get_clue(&self, word: &str) -> String {
+        match self.clues.get(word) {
+            Some(val) => val.clone(),
+            None => {
+                let length = word.chars().filter(|c| !c.is_whitespace()).count();
+                let mut hint = word.chars().next().unwrap_or(' ').to_string();
+                hint.push_str(&" _".repeat(length.max(1) - 1));
+                hint
+            }
+        }
+    }
+}
+#[cfg(test)]
+impl TitledWordSearch {
+    //Wraps a puzzle built by hand for the export tests
+    pub fn from_word_search(title: &str, word_search: WordSearch) -> TitledWordSearch {
+        let request = WordSearchRequest {
+            word: title.to_string(),
+            stype: SearchType::RelatedTo,
+            max_count: word_search.word_list.len(),
+            height: word_search.field[0].len(),
+            width: word_search.field.len(),
+            message: None,
+            kind: word_search.kind,
+            directions: Directions::all(),
+            title: None,
+            subtitle: None,
+            language: Language::English,
+            seed: None
+        };
+
+        get_titled_word_search(title.to_string(), word_search, Vec::new(), request)
+    }
+}
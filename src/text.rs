@@ -0,0 +1,286 @@
+use std::fs::File;
+use std::io::{ Error, Write };
+
+use crate::request::TitledWordSearch;
+use crate::word_search::{ PuzzleKind, MESSAGE_NOTE };
+
+static LINE_WIDTH: usize = 60;
+
+pub fn create_text(word_search_list: Vec<TitledWordSearch>, path: &str, options: &TextOptions) -> Result<(), Error> {
+    let mut file = File::create(path)?;
+    file.write_all(render_all(&word_search_list, options).as_bytes())?;
+
+    Ok(())
+}
+
+pub fn render_all(word_search_list: &Vec<TitledWordSearch>, options: &TextOptions) -> String {
+    let mut pages: Vec<String> = Vec::new();
+    for word_search in word_search_list {
+        pages.push(render(word_search, options));
+    }
+
+    if options.markdown {
+        pages.join("\n---\n\n")
+    }
+    else {
+        pages.join(&format!("\n{}\n\n", "=".repeat(LINE_WIDTH)))
+    }
+}
+
+pub fn render(word_search: &TitledWordSearch, options: &TextOptions) -> String {
+    let kind = word_search.word_search.kind;
+    let (grid, word_list, heading) = match kind {
+        PuzzleKind::WordSearch => (
+            render_grid(&word_search.word_search.field, options.coordinates, None),
+            render_word_list(&word_search.word_search.word_list),
+            "Words"
+        ),
+        PuzzleKind::FillIn => (
+            render_grid(&get_blank_field(word_search, false), options.coordinates, None),
+            render_by_length(&word_search.word_search.word_list),
+            "Words"
+        ),
+        PuzzleKind::CrissCross => (
+            render_grid(&get_blank_field(word_search, false), options.coordinates, None),
+            render_clues(word_search, options.coordinates),
+            "Clues"
+        )
+    };
+
+    let mut text = String::new();
+    if options.markdown {
+        text.push_str(&format!("## {}\n\n", word_search.title));
+        if !word_search.subtitle.is_empty() {
+            text.push_str(&format!("*{}*\n\n", word_search.subtitle));
+        }
+        text.push_str(&format!("```\n{}```\n\n", grid));
+        text.push_str(&format!("### {}\n\n```\n{}```\n", heading, word_list));
+    }
+    else {
+        text.push_str(&format!("{}\n{}\n\n", word_search.title, "-".repeat(word_search.title.chars().count())));
+        if !word_search.subtitle.is_empty() {
+            text.push_str(&format!("{}\n\n", word_search.subtitle));
+        }
+        text.push_str(&format!("{}\n", grid));
+        text.push_str(&format!("{}:\n\n{}", heading, word_list));
+    }
+    if word_search.word_search.message.is_some() {
+        text.push_str(&format!("\n{}\n", MESSAGE_NOTE));
+    }
+
+    if options.answers {
+        let answers = match kind {
+            PuzzleKind::WordSearch => render_grid(&word_search.word_search.field, options.coordinates, Some(&word_search.word_search.get_answer_cells())),
+            _ => render_grid(&get_blank_field(word_search, true), options.coordinates, None)
+        };
+        if options.markdown {
+            text.push_str(&format!("\n### Answers\n\n```\n{}```\n", answers));
+        }
+        else {
+            text.push_str(&format!("\nAnswers:\n\n{}", answers));
+        }
+
+        if let Some(message) = &word_search.word_search.message {
+            text.push_str(&format!("\nHidden message: {}\n", message));
+        }
+    }
+
+    text
+}
+
+fn render_grid(field: &[Vec<char>], coordinates: bool, answer_cells: Option<&Vec<Vec<bool>>>) -> String {
+    let w = field.len();
+    let h = field[0].len();
+    let row_label_width = h.to_string().len();
+
+    let mut grid = String::new();
+    if coordinates {
+        let mut labels: Vec<String> = Vec::new();
+        for x in 0..w {
+            labels.push(get_column_label(x).to_string());
+        }
+        grid.push_str(&format!("{}  {}\n", " ".repeat(row_label_width), labels.join(" ")));
+    }
+
+    for y in 0..h {
+        let mut row: Vec<String> = Vec::new();
+        for x in 0..w {
+            let mut c = field[x][y];
+            if let Some(cells) = answer_cells {
+                if !cells[x][y] {
+                    c = '.';
+                }
+            }
+            row.push(c.to_string());
+        }
+
+        if coordinates {
+            grid.push_str(&format!("{:>width$}  ", y + 1, width = row_label_width));
+        }
+        grid.push_str(&row.join(" "));
+        grid.push('\n');
+    }
+
+    grid
+}
+
+fn render_word_list(word_list: &Vec<String>) -> String {
+    if word_list.is_empty() {
+        return String::new();
+    }
+
+    let mut max_width = 0;
+    for word in word_list {
+        max_width = max_width.max(word.chars().count());
+    }
+    let col_width = max_width + 3;
+    let cols = (LINE_WIDTH / col_width).max(1);
+    let mut rows = word_list.len() / cols;
+    if !word_list.len().is_multiple_of(cols) {
+        rows += 1;
+    }
+
+    let mut text = String::new();
+    for y in 0..rows {
+        let mut line = String::new();
+        for x in 0..cols {
+            let index = x * rows + y; //Words run down the columns
+            if index >= word_list.len() {
+                break;
+            }
+            line.push_str(&format!("{:<width$}", word_list[index], width = col_width));
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+
+    text
+}
+
+//Fill-ins block the unused cells with "#", criss-crosses leave them out. Without answers the letters are "_"
+fn get_blank_field(word_search: &TitledWordSearch, answers: bool) -> Vec<Vec<char>> {
+    let unused = if word_search.word_search.kind == PuzzleKind::FillIn { '#' } else { ' ' };
+    let mut field = word_search.word_search.field.clone();
+    for column in field.iter_mut() {
+        for c in column.iter_mut() {
+            if *c == (0 as char) {
+                *c = unused;
+            }
+            else if !answers {
+                *c = '_';
+            }
+        }
+    }
+
+    field
+}
+
+fn render_by_length(word_list: &[String]) -> String {
+    let mut words = word_list.to_vec();
+    words.sort_by(|a, b| a.chars().count().cmp(&b.chars().count()).then(a.cmp(b)));
+
+    let mut text = String::new();
+    let mut length = 0;
+    for word in words {
+        let word_length = word.chars().count();
+        if word_length != length {
+            if length != 0 {
+                text.push('\n');
+            }
+            text.push_str(&format!("{} Letters:\n", word_length));
+            length = word_length;
+        }
+        text.push_str(&format!("    {}\n", word));
+    }
+
+    text
+}
+
+fn render_clues(word_search: &TitledWordSearch, coordinates: bool) -> String {
+    let numbers = word_search.word_search.get_numbers();
+    let mut text = String::new();
+    for (orientation, heading) in [(0, "Across"), (1, "Down")].iter() {
+        let mut clues: Vec<(usize, String)> = Vec::new();
+        for (placement, number) in word_search.word_search.placements.iter().zip(numbers.iter()) {
+            if placement.orientation != *orientation {
+                continue;
+            }
+
+            let mut clue = format!("{:>2}. ", number);
+            if coordinates {
+                clue.push_str(&format!("{}{} ", get_column_label(placement.x), placement.y + 1));
+            }
+            clue.push_str(&format!("{} ({})", word_search.get_clue(&placement.word), placement.length));
+            clues.push((*number, clue));
+        }
+        clues.sort();
+
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&format!("{}:\n", heading));
+        for (_, clue) in clues {
+            text.push_str(&format!("{}\n", clue));
+        }
+    }
+
+    text
+}
+
+fn get_column_label(index: usize) -> char {
+    (b'A' + (index % 26) as u8) as char
+}
+
+pub struct TextOptions {
+    pub coordinates: bool,
+    pub answers: bool,
+    pub markdown: bool
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_search::{ WordSearch, Placement };
+
+    #[test]
+    fn grid_has_coordinates_and_answer_dots() {
+        let field = vec![vec!['C', 'A'], vec!['A', 'T']];
+        assert_eq!(render_grid(&field, true, None), "   A B\n1  C A\n2  A T\n");
+
+        let cells = vec![vec![true, false], vec![true, false]];
+        assert_eq!(render_grid(&field, false, Some(&cells)), "C A\n. .\n");
+    }
+
+    #[test]
+    fn word_list_runs_down_the_columns() {
+        let words: Vec<String> = (1..=25).map(|i| format!("WORD{:02}", i)).collect();
+        let lines: Vec<String> = render_word_list(&words).lines().map(|line| line.to_string()).collect();
+        assert_eq!(lines.len(), 5); //Six columns of nine characters fit into a line
+        assert!(lines[0].starts_with("WORD01   WORD06"));
+        assert_eq!(lines[4], "WORD05   WORD10   WORD15   WORD20   WORD25");
+    }
+
+    #[test]
+    fn column_labels_wrap_after_z() {
+        let labels: String = [0, 1, 25, 26].iter().map(|index| get_column_label(*index)).collect();
+        assert_eq!(labels, "ABZA");
+    }
+
+    #[test]
+    fn answers_need_the_answer_key() {
+        let word_search = WordSearch {
+            field: vec![vec!['C', 'X'], vec!['A', 'T']],
+            word_list: vec![String::from("CA")],
+            placements: vec![Placement { word: String::from("CA"), x: 0, y: 0, orientation: 0, length: 2 }],
+            message: None,
+            kind: PuzzleKind::WordSearch
+        };
+        let puzzles = vec![TitledWordSearch::from_word_search("Letters", word_search)];
+
+        for markdown in [false, true].iter().cloned() {
+            let without = render_all(&puzzles, &TextOptions { coordinates: false, answers: false, markdown });
+            let with = render_all(&puzzles, &TextOptions { coordinates: false, answers: true, markdown });
+            assert!(!without.contains("Answers"), "{}", without);
+            assert!(with.contains("Answers") && with.contains("C A\n. .\n"), "{}", with);
+        }
+    }
+}
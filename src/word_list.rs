@@ -1,56 +1,180 @@
 use std::fmt::{ self, Display, Formatter };
 use std::error::Error;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::time::Instant;
 use rand::seq::SliceRandom;
-use reqwest;
-use serde::Deserialize;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{ Serialize, Deserialize };
 
-pub fn generate(start_word: &str, search_type: SearchType) -> Result<Vec<String>, SearchError> {
-    let request = match reqwest::blocking::get(&create_query(start_word, search_type)) {
+use crate::assets;
+
+pub fn generate(start_word: &str, search_type: SearchType, language: Language, random: &mut StdRng) -> Result<Vec<Word>, SearchError> {
+    if search_type == SearchType::Custom {
+        return load_custom(start_word, random);
+    }
+
+    let word_list = fetch(start_word, search_type, language, random)?;
+
+    if word_list.len() < 8 {
+        return Err(
+            SearchError::MyError(
+                format!("Not enough words could be found for \"{}\", please try a different word or category",
+                start_word)
+            )
+        );
+    }
+
+    Ok(word_list)
+}
+
+//Number of usable words Datamuse finds, so short lists can be reported before generating
+pub fn count_words(start_word: &str, search_type: SearchType, language: Language) -> Result<usize, SearchError> {
+    match fetch(start_word, search_type, language, &mut StdRng::from_entropy()) {
+        Ok(val) => Ok(val.len()),
+        Err(err) => Err(err)
+    }
+}
+
+fn fetch(start_word: &str, search_type: SearchType, language: Language, random: &mut StdRng) -> Result<Vec<Word>, SearchError> {
+    let query = create_query(start_word, search_type, language);
+    let start = Instant::now();
+    log::info!("Datamuse query: {}", query);
+    let request = match reqwest::blocking::get(&query) {
         Ok(val) => val,
-        Err(err) => return Err(SearchError::InternetError(Box::new(err)))
+        Err(err) => {
+            log::warn!("Datamuse query failed: {}", err);
+            return Err(SearchError::InternetError(Box::new(err)));
+        }
     };
     let word_list: Vec<WordResult> = match request.json() {
         Ok(val) => val,
-        Err(err) => return Err(SearchError::OtherError(Box::new(err)))
+        Err(err) => {
+            log::warn!("The Datamuse answer could not be read: {}", err);
+            return Err(SearchError::OtherError(Box::new(err)));
+        }
     };
+    let found = word_list.len();
     let word_list_parsed = match parse_tags(word_list) {
         Ok(val) => val,
         Err(err) => return Err(SearchError::OtherError(Box::new(err)))
     };
 
-    let mut word_list: Vec<String> = Vec::new();
+    let mut word_list: Vec<Word> = Vec::new();
     for word in word_list_parsed {
         if word.frequency < 300.0 && word.frequency != 0.0 && word.word.len() > 1 {
-            word_list.push(word.word);
+            word_list.push(word);
         }
     }
 
     let len = word_list.len();
-    word_list[0..len].shuffle(&mut rand::thread_rng());
+    word_list[0..len].shuffle(random);
+    log::debug!("Datamuse found {} words for \"{}\", {} are usable ({} ms)", found, start_word, len, start.elapsed().as_millis());
 
-    if len < 8 {
-        return Err(
-            SearchError::MyError(
-                format!("Not enough words could be found for \"{}\", please try a different word or category",
-                start_word)
-            )
-        );
+    Ok(word_list)
+}
+
+//Reads a word list with one word per line, optionally followed by a picture: "word, picture.png".
+//Pictures are relative to the word list. Instead of a path the name of a built in list can be used
+pub fn load_custom(path: &str, random: &mut StdRng) -> Result<Vec<Word>, SearchError> {
+    let (text, list_path) = match fs::read_to_string(path) {
+        Ok(val) => (val, PathBuf::from(path)),
+        Err(err) => match assets::load_word_list(path.trim()) {
+            Some((text, list_path)) => {
+                log::debug!("Using the {} word list \"{}\"", if list_path.is_some() { "data directory" } else { "built in" }, path);
+                (text.into_owned(), list_path.unwrap_or_default())
+            },
+            None => return Err(SearchError::MyError(format!("The word list \"{}\" could not be read: {}", path, err)))
+        }
+    };
+    let directory = list_path.parent().unwrap_or(Path::new(""));
+
+    let mut word_list: Vec<Word> = Vec::new();
+    for line in text.lines() {
+        let mut parts = line.splitn(2, ',');
+        let word = parts.next().unwrap_or("").trim();
+        if word.is_empty() {
+            continue;
+        }
+
+        let mut image = None;
+        if let Some(file) = parts.next().map(|file| file.trim()).filter(|file| !file.is_empty()) {
+            let image_path = directory.join(file);
+            if !image_path.is_file() {
+                return Err(
+                    SearchError::MyError(
+                        format!("The picture \"{}\" for \"{}\" doesn't exist", image_path.display(), word)
+                    )
+                );
+            }
+            image = Some(image_path.to_string_lossy().to_string());
+        }
+
+        word_list.push(Word {
+            word: word.to_string(),
+            frequency: 0.0,
+            definition: None,
+            image
+        });
     }
 
+    if word_list.is_empty() {
+        return Err(SearchError::MyError(format!("The word list \"{}\" is empty", path)));
+    }
+    word_list.shuffle(random);
+
     Ok(word_list)
 }
 
-fn create_query(word: &str, search_type: SearchType) -> String {
+//Reads clues from a file with one "word: clue" pair per line
+pub fn load_clues(path: &str) -> Result<HashMap<String, String>, SearchError> {
+    let text = match fs::read_to_string(path) {
+        Ok(val) => val,
+        Err(err) => return Err(SearchError::OtherError(Box::new(err)))
+    };
+
+    let mut clues: HashMap<String, String> = HashMap::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim() == "" {
+            continue;
+        }
+
+        let mut parts = line.splitn(2, ':');
+        let word = parts.next().unwrap_or("").trim();
+        let clue = parts.next().unwrap_or("").trim();
+        if word.is_empty() || clue.is_empty() {
+            return Err(
+                SearchError::MyError(
+                    format!("Line {} of \"{}\" is not a \"word: clue\" pair", i + 1, path)
+                )
+            );
+        }
+
+        clues.insert(word.to_uppercase(), clue.to_string());
+    }
+
+    Ok(clues)
+}
+
+fn create_query(word: &str, search_type: SearchType, language: Language) -> String {
     let arg = match search_type {
         SearchType::RelatedTo => format!("rel_trg={}", word),
         SearchType::RhymesWith => format!("rel_rhy={}", word),
         SearchType::EndsWith => format!("sp=*{}", word),
         SearchType::SoundsLike => format!("sl={}", word),
         SearchType::BlankWord => format!("rel_bgb={}", word),
-        SearchType::WordBlank => format!("rel_bga={}", word)
+        SearchType::WordBlank => format!("rel_bga={}", word),
+        SearchType::Custom => String::new()
+    };
+
+    let vocabulary = match language {
+        Language::English => "",
+        Language::Spanish => "&v=es"
     };
 
-    format!("https://api.datamuse.com/words?{}&md=f", arg)
+    format!("https://api.datamuse.com/words?{}{}&md=fd", arg, vocabulary)
 }
 
 fn parse_tags(word_list: Vec<WordResult>) -> Result<Vec<Word>, SearchError> {
@@ -58,30 +182,75 @@ fn parse_tags(word_list: Vec<WordResult>) -> Result<Vec<Word>, SearchError> {
     for word_res in word_list {
         let word = word_res.word.clone();
         let mut freq: f64 = 0.0;
-        if word_res.tags.len() != 0 {
+        if !word_res.tags.is_empty() {
             freq = match word_res.tags[0].replace("f:", "").parse() {
                 Ok(val) => val,
                 Err(err) => return Err(SearchError::OtherError(Box::new(err)))
             };
         }
 
+        let mut definition = None;
+        if let Some(defs) = &word_res.defs {
+            if !defs.is_empty() {
+                definition = Some(parse_definition(&defs[0], &word));
+            }
+        }
+
         word_list_parsed.push(Word {
-            word: word,
-            frequency: freq
+            word,
+            frequency: freq,
+            definition,
+            image: None
         });
     }
 
     Ok(word_list_parsed)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//Definitions look like "n\tthe definition", the word itself is blanked out so the clue doesn't give it away
+fn parse_definition(definition: &str, word: &str) -> String {
+    let definition = match definition.find('\t') {
+        Some(index) => &definition[index + 1..],
+        None => definition
+    };
+
+    let mut parsed = String::new();
+    let lower = definition.to_ascii_lowercase();
+    let word = word.to_ascii_lowercase();
+    let mut last = 0;
+    for (index, _) in lower.match_indices(&word) {
+        let end = index + word.len();
+        let before = lower[..index].chars().next_back().is_some_and(|c| c.is_alphabetic());
+        let after = lower[end..].chars().next().is_some_and(|c| c.is_alphabetic());
+        if word.is_empty() || before || after { //Only whole words
+            continue;
+        }
+
+        parsed.push_str(&definition[last..index]);
+        parsed.push_str("___");
+        last = end;
+    }
+    parsed.push_str(&definition[last..]);
+
+    parsed.trim().to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SearchType {
     RelatedTo,
     RhymesWith,
     EndsWith,
     SoundsLike,
     BlankWord,
-    WordBlank
+    WordBlank,
+    Custom //The base word is the path of a word list file
+}
+
+//Vocabularies Datamuse can search, custom word lists can use any language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    English,
+    Spanish
 }
 
 #[derive(Debug)]
@@ -95,12 +264,16 @@ pub enum SearchError<> {
 struct WordResult {
     word: String,
     _score: Option<u64>,
-    tags: Vec<String>
+    tags: Vec<String>,
+    defs: Option<Vec<String>>
 }
 
-struct Word {
-    word: String,
-    frequency: f64
+#[derive(Clone)]
+pub struct Word {
+    pub word: String,
+    pub frequency: f64,
+    pub definition: Option<String>,
+    pub image: Option<String> //Path of a picture shown instead of the word
 }
 
 impl Error for SearchError {}
@@ -115,4 +288,73 @@ impl Display for SearchError {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn definitions_hide_only_the_whole_word() {
+        assert_eq!(parse_definition("n\tA cat is a small cat-like animal", "cat"), "A ___ is a small ___-like animal");
+        assert_eq!(parse_definition("v\tConcatenate the CAT files", "Cat"), "Concatenate the ___ files");
+        assert_eq!(parse_definition("  No part of speech  ", "dog"), "No part of speech");
+    }
+
+    #[test]
+    fn clue_files_are_word_clue_pairs() {
+        let path = std::env::temp_dir().join(format!("wordsearch_clues_{}.txt", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+
+        fs::write(&path, "cat: Says meow\n\n  dog :Barks  \nkey: value: with colon\n").unwrap();
+        let clues = load_clues(&path).unwrap();
+        assert_eq!(clues.len(), 3);
+        assert_eq!(clues["CAT"], "Says meow");
+        assert_eq!(clues["DOG"], "Barks");
+        assert_eq!(clues["KEY"], "value: with colon");
+
+        fs::write(&path, "cat: Says meow\ndog\n").unwrap();
+        let result = load_clues(&path);
+        let _ = fs::remove_file(&path);
+        match result {
+            Err(SearchError::MyError(err)) => assert!(err.starts_with("Line 2 of"), "{}", err),
+            _ => panic!("The line without a clue wasn't reported")
+        }
+    }
+
+    #[test]
+    fn custom_lists_find_pictures_next_to_the_list() {
+        let directory = std::env::temp_dir().join(format!("wordsearch_custom_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("cat.png"), "").unwrap();
+        let path = directory.join("pets.txt").to_string_lossy().to_string();
+        let mut random = StdRng::seed_from_u64(0);
+
+        fs::write(&path, "Cat, cat.png\n\n  Dog  \n").unwrap();
+        let mut words = load_custom(&path, &mut random).unwrap();
+        words.sort_by(|a, b| a.word.cmp(&b.word));
+        assert_eq!(words.iter().map(|word| word.word.as_str()).collect::<Vec<&str>>(), vec!["Cat", "Dog"]);
+        assert_eq!(words[0].image.as_deref(), Some(directory.join("cat.png").to_string_lossy().as_ref()));
+        assert!(words[1].image.is_none());
+
+        fs::write(&path, "Cat, cat.png\nDog, dog.png\n").unwrap();
+        let missing = load_custom(&path, &mut random);
+        fs::write(&path, "\n  \n").unwrap();
+        let empty = load_custom(&path, &mut random);
+        let _ = fs::remove_dir_all(&directory);
+        let unreadable = load_custom(&path, &mut random);
+
+        match missing {
+            Err(SearchError::MyError(err)) => assert!(err.contains("dog.png") && err.contains("\"Dog\""), "{}", err),
+            _ => panic!("The missing picture wasn't reported")
+        }
+        match empty {
+            Err(SearchError::MyError(err)) => assert!(err.ends_with("is empty"), "{}", err),
+            _ => panic!("The empty list wasn't reported")
+        }
+        match unreadable {
+            Err(SearchError::MyError(err)) => assert!(err.contains(&path), "{}", err),
+            _ => panic!("The deleted list wasn't reported")
+        }
+    }
+}
@@ -2,8 +2,10 @@ use std::sync::Mutex;
 use std::thread;
 use std::iter::FromIterator;
 use rand::prelude::*;
+use serde::{ Serialize, Deserialize };
 
 static MAX_ITER: u32 = 1000;
+pub const MESSAGE_NOTE: &str = "The letters left over after finding all words spell a hidden message."; //Printed below puzzles with a hidden message
 
 lazy_static! {
     static ref ITERATIONS: Mutex<u32> = {
@@ -15,35 +17,93 @@ lazy_static! {
     };
 }
 
-pub fn generate(word_list: &Vec<String>, max_count: usize, height: usize, width: usize) -> Option<WordSearch> {
-    let mut field: Vec<Vec<char>> = new_field(height, width);
-    let mut random = rand::thread_rng();
-    let word_list = word_list.clone();
-    let word_list: Vec<String> = word_list.into_iter().filter(|word| word.len() <= width && word.len() <= height).collect();
+pub fn generate(word_list: &[String], options: &GenerateOptions, random: &mut StdRng) -> Option<WordSearch> {
+    match options.kind {
+        PuzzleKind::WordSearch => generate_word_search(word_list, options.max_count, options.width, options.height, options.message, options.directions, random),
+        PuzzleKind::FillIn | PuzzleKind::CrissCross => generate_crossword(word_list, options.max_count, options.width, options.height, options.kind, random)
+    }
+}
+
+//With a hidden message exactly as many cells as the message has letters are left empty,
+//the message is then written into them in reading order instead of random letters
+fn generate_word_search(word_list: &[String], max_count: usize, width: usize, height: usize, message: Option<&str>, directions: Directions, random: &mut StdRng) -> Option<WordSearch> {
+    let mut field: Vec<Vec<char>> = new_field(width, height);
+    let orientations = directions.get_orientations();
+    if orientations.is_empty() {
+        return None;
+    }
+    let word_list: Vec<String> = word_list.iter().filter(|word| word.len() <= width && word.len() <= height).cloned().collect();
+    let message_letters = get_message_letters(message.unwrap_or(""));
+    if message_letters.len() > width * height {
+        return None;
+    }
 
     let mut search_list: Vec<String> = Vec::new();
+    let mut placements: Vec<Placement> = Vec::new();
 
-    for word in word_list {
-        if check_stop() {
-            return None;
-        }
-        else if search_list.len() >= max_count {
-            break;
+    if !message_letters.is_empty() {
+        //Every word is placed where it fills the most empty cells, so the maximum word count leaves as few cells as possible
+        while count_empty(&field) > message_letters.len() {
+            if check_stop() {
+                return None;
+            }
+            else if search_list.len() >= max_count {
+                log::debug!("{} words leave {} cells for the {} letters of the hidden message", search_list.len(), count_empty(&field), message_letters.len());
+                set_iter(0);
+                return None;
+            }
+
+            match fill_for_message(&field, &word_list, &search_list, &orientations, message_letters.len(), directions.backwards, random) {
+                Some((val, placement)) => {
+                    set_count(get_count() + 1);
+                    field = val;
+                    search_list.push(placement.word.clone());
+                    placements.push(placement);
+                },
+                None => {
+                    log::debug!("No words fit the {} cells left for the hidden message", count_empty(&field));
+                    set_iter(0);
+                    return None;
+                }
+            }
         }
 
-        let word_original = word.clone();
-        let mut word = word.to_uppercase().replace(" ", "");
-        if random.gen::<f32>() < 0.25 {
-            word = String::from_iter(word.chars().rev());
+        let mut letters = message_letters.into_iter();
+        for y in 0..field[0].len() {
+            for column in field.iter_mut() {
+                if column[y] == (0 as char) {
+                    column[y] = letters.next().unwrap_or('X');
+                }
+            }
         }
+    }
+    else {
+        for word in &word_list {
+            if check_stop() {
+                return None;
+            }
+            else if search_list.len() >= max_count {
+                break;
+            }
 
-        match add_word(&field, &word, &mut random) {
-            Some(val) => {
-                set_count(get_count() + 1);
-                field = val;
-                search_list.push(word_original.to_uppercase());
-            },
-            None => ()
+            let word_original = word.clone();
+            let mut word = word.to_uppercase().replace(" ", "");
+            if directions.backwards && random.gen::<f32>() < 0.25 {
+                word = String::from_iter(word.chars().rev());
+            }
+
+            match add_word(&field, &word, &orientations, random) {
+                Some((val, placement)) => {
+                    set_count(get_count() + 1);
+                    field = val;
+                    search_list.push(word_original.to_uppercase());
+                    placements.push(Placement {
+                        word: word_original.to_uppercase(), //The placed word may be reversed and without spaces
+                        ..placement
+                    });
+                },
+                None => log::debug!("No place found for {}", word_original)
+            }
         }
     }
 
@@ -59,12 +119,107 @@ pub fn generate(word_list: &Vec<String>, max_count: usize, height: usize, width:
 
     Some(
         WordSearch {
-            field: field,
-            word_list: search_list
+            field,
+            word_list: search_list,
+            placements,
+            message: message.map(|val| val.trim().to_string()).filter(|val| !get_message_letters(val).is_empty()),
+            kind: PuzzleKind::WordSearch
         }
     )
 }
 
+//Words only run across and down and every word after the first has to cross one that is already placed.
+//Cells without a letter stay empty
+fn generate_crossword(word_list: &[String], max_count: usize, width: usize, height: usize, kind: PuzzleKind, random: &mut StdRng) -> Option<WordSearch> {
+    let mut field: Vec<Vec<char>> = new_field(width, height);
+    let mut word_list: Vec<String> = word_list.iter()
+        .map(|word| word.to_uppercase())
+        .filter(|word| word.chars().all(|c| c.is_alphabetic()) && word.chars().count() <= width.max(height))
+        .collect();
+    word_list.sort_by_key(|word| std::cmp::Reverse(word.chars().count())); //Long words first leave more room for crossings
+
+    let mut search_list: Vec<String> = Vec::new();
+    let mut placements: Vec<Placement> = Vec::new();
+
+    for word in word_list {
+        if check_stop() {
+            return None;
+        }
+        else if search_list.len() >= max_count {
+            break;
+        }
+        else if search_list.contains(&word) {
+            continue;
+        }
+        set_iter(get_iter() + 1);
+
+        let letters: Vec<char> = word.chars().collect();
+        let mut options: Vec<(usize, usize, usize, usize)> = Vec::new(); //x, y, orientation and crossings
+        for orientation in 0..2 {
+            for x in 0..field.len() {
+                for y in 0..field[0].len() {
+                    if let Some(crossings) = try_cross(&field, &letters, x, y, orientation) {
+                        if crossings > 0 || placements.is_empty() {
+                            options.push((x, y, orientation, crossings));
+                        }
+                    }
+                }
+            }
+        }
+
+        //The first word goes through the middle, the others pick randomly among the ones with the most crossings
+        let most = options.iter().map(|option| option.3).max().unwrap_or(0);
+        let mut options: Vec<(usize, usize, usize, usize)> = options.into_iter().filter(|option| option.3 == most).collect();
+        if placements.is_empty() {
+            let (middle_x, middle_y) = (field.len().saturating_sub(letters.len()) / 2, field[0].len() / 2);
+            options.retain(|option| option.0 == middle_x && option.1 == middle_y && option.2 == 0);
+        }
+        let (x, y, orientation, _) = match options.choose(random) {
+            Some(val) => *val,
+            None => {
+                log::debug!("No place found for {}", word);
+                continue;
+            }
+        };
+
+        for i in 0..letters.len() {
+            match orientation {
+                0 => field[x + i][y] = letters[i],
+                _ => field[x][y + i] = letters[i]
+            }
+        }
+        set_count(get_count() + 1);
+        search_list.push(word.clone());
+        placements.push(Placement {
+            word,
+            x,
+            y,
+            orientation,
+            length: letters.len()
+        });
+    }
+
+    set_iter(0);
+
+    if placements.is_empty() {
+        return None;
+    }
+
+    Some(
+        WordSearch {
+            field,
+            word_list: search_list,
+            placements,
+            message: None,
+            kind
+        }
+    )
+}
+
+pub fn get_message_letters(message: &str) -> Vec<char> {
+    message.to_uppercase().chars().filter(|c| c.is_alphabetic()).collect()
+}
+
 pub fn get_count() -> u32 {
     match COUNT.lock() {
         Ok(val) => *val,
@@ -117,14 +272,148 @@ pub fn set_count(count: u32) {
     }
 }
 
-fn add_word(field: &Vec<Vec<char>>, word: &str, random: &mut ThreadRng) -> Option<Vec<Vec<char>>> {
-    let start_or = (random.gen::<f32>() * 4.0) as usize; //Orientations: horizontal (0), vertical (1), diagonal down (2), diagonal up (3),
-    let or_set = vec![
-        start_or,
-        start_or + 1 % 4,
-        start_or + 2 % 4,
-        start_or + 3 % 4
-    ];
+//Returns how many letters the word shares with the words already placed, if it fits without touching any other word
+fn try_cross(field: &[Vec<char>], word: &[char], x: usize, y: usize, orientation: usize) -> Option<usize> {
+    let w = field.len() as i32;
+    let h = field[0].len() as i32;
+    let (dx, dy) = if orientation == 0 { (1, 0) } else { (0, 1) };
+    let get = |x: i32, y: i32| -> char {
+        if x < 0 || y < 0 || x >= w || y >= h {
+            return 0 as char;
+        }
+        field[x as usize][y as usize]
+    };
+
+    let (x, y) = (x as i32, y as i32);
+    let len = word.len() as i32;
+    if x + dx * (len - 1) >= w || y + dy * (len - 1) >= h {
+        return None;
+    }
+    if get(x - dx, y - dy) != (0 as char) || get(x + dx * len, y + dy * len) != (0 as char) {
+        return None;
+    }
+
+    let mut crossings = 0;
+    for i in 0..len {
+        let (cx, cy) = (x + dx * i, y + dy * i);
+        let c = get(cx, cy);
+        if c == word[i as usize] {
+            crossings += 1;
+        }
+        else if c != (0 as char) {
+            return None;
+        }
+        else if get(cx + dy, cy + dx) != (0 as char) || get(cx - dy, cy - dx) != (0 as char) { //New letters can't be next to other words
+            return None;
+        }
+    }
+
+    if crossings == len {
+        return None;
+    }
+
+    Some(crossings as usize)
+}
+
+fn count_empty(field: &Vec<Vec<char>>) -> usize {
+    let mut count = 0;
+    for column in field {
+        count += column.iter().filter(|c| **c == (0 as char)).count();
+    }
+
+    count
+}
+
+//Finds the unused word and position that fills the most empty cells without taking any the message needs.
+//Words and cells are tried in random order, so one of the equally good positions is picked at random
+fn fill_for_message(field: &Vec<Vec<char>>, word_list: &[String], search_list: &[String], orientations: &[usize], message_length: usize, backwards: bool, random: &mut StdRng) -> Option<(Vec<Vec<char>>, Placement)> {
+    set_iter(get_iter() + 1);
+
+    let (w, h) = (field.len(), field[0].len());
+    let (start_x, start_y) = (random.gen_range(0, w), random.gen_range(0, h));
+    let mut words: Vec<&String> = word_list.iter().collect();
+    words.shuffle(random);
+
+    let empty = count_empty(field);
+    let mut best: Option<(String, Placement)> = None; //The letters in placing order and where they go
+    let mut best_count = 0;
+    for word_original in words {
+        let word_original = word_original.to_uppercase();
+        if search_list.contains(&word_original) {
+            continue;
+        }
+
+        let mut letters: Vec<char> = word_original.chars().filter(|c| *c != ' ').collect();
+        if backwards && random.gen::<f32>() < 0.25 {
+            letters.reverse();
+        }
+        for orientation in orientations.iter().cloned() {
+            for i in 0..w {
+                for j in 0..h {
+                    let (x, y) = ((start_x + i) % w, (start_y + j) % h);
+                    let filled = match count_filled(field, &letters, x, y, orientation) {
+                        Some(val) => val,
+                        None => continue
+                    };
+
+                    if filled > best_count && empty - filled >= message_length {
+                        best_count = filled;
+                        best = Some((letters.iter().collect(), Placement {
+                            word: word_original.clone(),
+                            x,
+                            y,
+                            orientation,
+                            length: letters.len()
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    best.and_then(|(word, placement)| try_add(field, &word, placement.x, placement.y, placement.orientation).map(|val| (val, placement)))
+}
+
+//Returns how many empty cells the word would fill, None if it doesn't fit at the position. Uses the same bounds as try_add
+fn count_filled(field: &[Vec<char>], word: &[char], x: usize, y: usize, orientation: usize) -> Option<usize> {
+    let (w, h, length) = (field.len(), field[0].len(), word.len());
+    let fits = match orientation {
+        0 => x + length < w,
+        1 => y + length < h,
+        2 => x + length < w && y + length < h,
+        3 => x + length < w && y >= length,
+        _ => false
+    };
+    if !fits {
+        return None;
+    }
+
+    let mut filled = 0;
+    for (i, c) in word.iter().enumerate() {
+        let (cell_x, cell_y) = match orientation {
+            0 => (x + i, y),
+            1 => (x, y + i),
+            2 => (x + i, y + i),
+            _ => (x + i, y - i)
+        };
+
+        if field[cell_x][cell_y] == (0 as char) {
+            filled += 1;
+        }
+        else if field[cell_x][cell_y] != *c {
+            return None;
+        }
+    }
+
+    Some(filled)
+}
+
+fn add_word(field: &[Vec<char>], word: &str, orientations: &[usize], random: &mut StdRng) -> Option<(Vec<Vec<char>>, Placement)> {
+    let start_or = (random.gen::<f32>() * (orientations.len() as f32)) as usize; //Orientations: horizontal (0), vertical (1), diagonal down (2), diagonal up (3),
+    let mut or_set: Vec<usize> = Vec::new();
+    for i in 0..orientations.len() {
+        or_set.push(orientations[(start_or + i) % orientations.len()]);
+    }
     let start_w = (random.gen::<f32>() * (field.len() as f32)) as usize;
     let start_h = (random.gen::<f32>() * (field[0].len() as f32)) as usize;
 
@@ -139,7 +428,7 @@ fn add_word(field: &Vec<Vec<char>>, word: &str, random: &mut ThreadRng) -> Optio
 
                 match try_add(field, word, x, y, orientation) {
                     Some(val) => {
-                        return Some(val)
+                        return Some((val, Placement { word: word.to_string(), x, y, orientation, length: word.chars().count() }))
                     },
                     None => continue
                 }
@@ -154,7 +443,7 @@ fn add_word(field: &Vec<Vec<char>>, word: &str, random: &mut ThreadRng) -> Optio
 
                 match try_add(field, word, x, y, orientation) {
                     Some(val) => {
-                        return Some(val)
+                        return Some((val, Placement { word: word.to_string(), x, y, orientation, length: word.chars().count() }))
                     },
                     None => continue
                 }
@@ -165,12 +454,12 @@ fn add_word(field: &Vec<Vec<char>>, word: &str, random: &mut ThreadRng) -> Optio
     None
 }
 
-fn try_add(field: &Vec<Vec<char>>, word: &str, x: usize, y: usize, orientation: usize) -> Option<Vec<Vec<char>>> {
+fn try_add(field: &[Vec<char>], word: &str, x: usize, y: usize, orientation: usize) -> Option<Vec<Vec<char>>> {
     let word: Vec<char> = word.chars().collect();
     let w = field.len();
     let h = field[0].len();
     
-    let mut field_copy = field.clone();
+    let mut field_copy = field.to_vec();
 
     match orientation {
         0 => {
@@ -244,5 +533,216 @@ fn new_field(width: usize, height: usize) -> Vec<Vec<char>> {
 #[derive(Clone)]
 pub struct WordSearch {
     pub field: Vec<Vec<char>>,
-    pub word_list: Vec<String>
-}
\ No newline at end of file
+    pub word_list: Vec<String>,
+    pub placements: Vec<Placement>,
+    pub message: Option<String>, //Spelled by the letters that aren't part of any word
+    pub kind: PuzzleKind
+}
+
+pub struct GenerateOptions<'a> {
+    pub max_count: usize,
+    pub width: usize,
+    pub height: usize,
+    pub message: Option<&'a str>, //Only used by word searches
+    pub kind: PuzzleKind,
+    pub directions: Directions
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PuzzleKind {
+    WordSearch,
+    FillIn, //Blank grid, the words are listed by length
+    CrissCross //Blank grid with numbered clues
+}
+
+//Only used by word searches, fill-ins and criss-crosses always run across and down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Directions {
+    pub horizontal: bool,
+    pub vertical: bool,
+    pub diagonal: bool,
+    pub backwards: bool
+}
+
+#[derive(Clone)]
+pub struct Placement {
+    pub word: String,
+    pub x: usize, //Position of the first placed letter (the last letter of the word if it was reversed)
+    pub y: usize,
+    pub orientation: usize,
+    pub length: usize
+}
+
+impl WordSearch {
+    pub fn get_answer_cells(&self) -> Vec<Vec<bool>> {
+        let mut cells = vec![vec![false; self.field[0].len()]; self.field.len()];
+        for placement in &self.placements {
+            for (x, y) in placement.cells() {
+                cells[x][y] = true;
+            }
+        }
+
+        cells
+    }
+
+    //Numbers the start of every word in reading order like a crossword, returns the number of every placement
+    pub fn get_numbers(&self) -> Vec<usize> {
+        let mut starts: Vec<(usize, usize)> = self.placements.iter().map(|placement| (placement.y, placement.x)).collect();
+        starts.sort();
+        starts.dedup();
+
+        let mut numbers: Vec<usize> = Vec::new();
+        for placement in &self.placements {
+            let index = starts.iter().position(|start| *start == (placement.y, placement.x)).unwrap_or(0);
+            numbers.push(index + 1);
+        }
+
+        numbers
+    }
+}
+
+impl Directions {
+    pub fn all() -> Directions {
+        Directions {
+            horizontal: true,
+            vertical: true,
+            diagonal: true,
+            backwards: true
+        }
+    }
+
+    fn get_orientations(&self) -> Vec<usize> {
+        let mut orientations: Vec<usize> = Vec::new();
+        if self.horizontal {
+            orientations.push(0);
+        }
+        if self.vertical {
+            orientations.push(1);
+        }
+        if self.diagonal {
+            orientations.push(2);
+            orientations.push(3);
+        }
+
+        orientations
+    }
+}
+
+impl Placement {
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for i in 0..self.length {
+            let cell = match self.orientation {
+                0 => (self.x + i, self.y),
+                1 => (self.x, self.y + i),
+                2 => (self.x + i, self.y + i),
+                _ => (self.x + i, self.y - i)
+            };
+            cells.push(cell);
+        }
+
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static ANIMALS: [&str; 12] = ["Giraffe", "Monkey", "Whale", "Camel", "Mouse", "Bear", "Turtle", "Rabbit", "Koala", "Eagle", "Fox", "Polar Bear"];
+
+    fn get_options(max_count: usize, message: &str) -> GenerateOptions<'_> {
+        GenerateOptions {
+            max_count,
+            width: 8,
+            height: 8,
+            message: Some(message),
+            kind: PuzzleKind::WordSearch,
+            directions: Directions::all()
+        }
+    }
+
+    #[test]
+    fn leftover_letters_spell_the_message() {
+        let word_list: Vec<String> = ANIMALS.iter().map(|word| word.to_string()).collect();
+        let mut generated = 0;
+        for seed in 0..20 {
+            let mut random = StdRng::seed_from_u64(seed);
+            let word_search = match generate(&word_list, &get_options(20, "Hello, World!"), &mut random) {
+                Some(val) => val,
+                None => continue //Not every seed leaves the right number of cells
+            };
+            generated += 1;
+
+            let answers = word_search.get_answer_cells();
+            let mut leftover = String::new();
+            for y in 0..8 {
+                for (column, letters) in answers.iter().zip(&word_search.field) {
+                    if !column[y] {
+                        leftover.push(letters[y]);
+                    }
+                }
+            }
+            assert_eq!(leftover, "HELLOWORLD");
+            assert_eq!(word_search.message, Some(String::from("Hello, World!")));
+        }
+
+        assert!(generated > 0);
+    }
+
+    #[test]
+    fn message_respects_the_word_count() {
+        let word_list: Vec<String> = ANIMALS.iter().map(|word| word.to_string()).collect();
+        for seed in 0..20 {
+            let mut random = StdRng::seed_from_u64(seed);
+            if let Some(word_search) = generate(&word_list, &get_options(5, "Hello"), &mut random) {
+                assert!(word_search.word_list.len() <= 5);
+            }
+        }
+    }
+
+    #[test]
+    fn message_cells_are_never_filled() {
+        let field = new_field(5, 5);
+        let words = vec![String::from("Cat"), String::from("Giraffe")];
+        let fill = |search_list: &[String], message_length: usize| {
+            fill_for_message(&field, &words, search_list, &[0, 1, 2, 3], message_length, false, &mut StdRng::seed_from_u64(0))
+        };
+
+        let (filled, placement) = fill(&[], 22).unwrap();
+        assert_eq!(placement.word, "CAT");
+        assert_eq!(count_empty(&filled), 22);
+        assert!(fill(&[], 23).is_none()); //CAT would take one of the message cells and GIRAFFE doesn't fit
+        assert!(fill(&[String::from("CAT")], 0).is_none()); //Already in the puzzle
+    }
+
+    #[test]
+    fn crossing_words_share_letters_without_touching() {
+        let mut field = new_field(5, 5);
+        for (x, c) in "CAT".chars().enumerate() {
+            field[x][1] = c;
+        }
+        let cross = |word: &str, x: usize, y: usize, orientation: usize| try_cross(&field, &word.chars().collect::<Vec<char>>(), x, y, orientation);
+
+        assert_eq!(cross("HAT", 1, 0, 1), Some(1));
+        assert_eq!(cross("TOP", 2, 1, 1), Some(1));
+        assert_eq!(cross("OWL", 0, 3, 0), Some(0));
+        assert_eq!(cross("HOT", 1, 0, 1), None); //Different letter in a shared cell
+        assert_eq!(cross("AT", 3, 0, 1), None); //Next to the end of CAT
+        assert_eq!(cross("CAT", 0, 1, 0), None); //Only letters that are already placed
+        assert_eq!(cross("BEAR", 2, 3, 0), None); //Past the edge
+    }
+
+    #[test]
+    fn clue_numbers_follow_reading_order() {
+        let placement = |word: &str, x: usize, y: usize, orientation: usize| Placement { word: word.to_string(), x, y, orientation, length: word.len() };
+        let word_search = WordSearch {
+            field: new_field(5, 5),
+            word_list: Vec::new(),
+            placements: vec![placement("CAT", 0, 1, 0), placement("TOP", 2, 1, 1), placement("HAT", 1, 0, 1), placement("CUP", 0, 1, 1)],
+            message: None,
+            kind: PuzzleKind::CrissCross
+        };
+        assert_eq!(word_search.get_numbers(), vec![2, 3, 1, 2]);
+    }
+}